@@ -0,0 +1,87 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Fields};
+
+/// Derives ```hlocl::buffer::DeviceElement``` for a ```#[repr(C)]```, ```#[repr(transparent)]``` or ```#[repr(packed)]``` struct.
+/// 
+/// Every field must implement ```DeviceElement```, and the struct must not contain any padding bytes.
+#[proc_macro_derive(DeviceElement)]
+pub fn derive_device_element (input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match device_element(&input) {
+        Ok(x) => x.into(),
+        Err(e) => e.to_compile_error().into()
+    }
+}
+
+fn device_element (input: &DeriveInput) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(input.generics.span(), "`DeviceElement` can't be derived for generic types"));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields.named.iter().collect::<Vec<_>>(),
+            Fields::Unnamed(fields) => fields.unnamed.iter().collect::<Vec<_>>(),
+            Fields::Unit => Vec::new()
+        },
+        Data::Enum(data) => return Err(Error::new(data.enum_token.span, "`DeviceElement` can't be derived for enums")),
+        Data::Union(data) => return Err(Error::new(data.union_token.span, "`DeviceElement` can't be derived for unions"))
+    };
+
+    if !has_stable_layout(input)? {
+        return Err(Error::new(ident.span(), "`DeviceElement` requires `#[repr(C)]`, `#[repr(transparent)]` or `#[repr(packed)]`"));
+    }
+
+    let field_checks = fields.iter().map(|field| {
+        let ty = &field.ty;
+        quote_spanned! { ty.span() => assert_element::<#ty>(); }
+    });
+
+    let field_sizes = fields.iter().map(|field| {
+        let ty = &field.ty;
+        quote! { + ::core::mem::size_of::<#ty>() }
+    });
+
+    let padding_msg = format!("`{ident}` contains padding bytes");
+    let zst_msg = format!("`{ident}` is zero-sized");
+    Ok(quote! {
+        const _: () = {
+            #[allow(dead_code)]
+            fn assert_element<T: ::hlocl::buffer::DeviceElement> () {}
+            #[allow(dead_code)]
+            fn assert_fields () {
+                #(#field_checks)*
+            }
+
+            assert!(::core::mem::size_of::<#ident>() == 0 #(#field_sizes)*, #padding_msg);
+            assert!(::core::mem::size_of::<#ident>() != 0, #zst_msg);
+        };
+
+        unsafe impl ::hlocl::buffer::DeviceElement for #ident {}
+    })
+}
+
+fn has_stable_layout (input: &DeriveInput) -> syn::Result<bool> {
+    let mut stable = false;
+
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") || meta.path.is_ident("transparent") || meta.path.is_ident("packed") {
+                stable = true;
+            }
+
+            // skip arguments such as `packed(2)` or `align(8)`
+            if meta.input.peek(syn::token::Paren) {
+                let _content;
+                syn::parenthesized!(_content in meta.input);
+            }
+
+            Ok(())
+        })?;
+    }
+
+    Ok(stable)
+}
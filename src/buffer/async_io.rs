@@ -0,0 +1,376 @@
+#[cfg(test)]
+extern crate std;
+
+use core::{pin::Pin, ptr::NonNull, task::{Context as TaskContext, Poll}};
+use std::io::SeekFrom;
+use alloc::{vec::Vec, boxed::Box};
+use futures::{AsyncRead, AsyncWrite, AsyncSeek, AsyncReadExt, AsyncWriteExt, Future, ready};
+use crate::prelude::{Result, Context, Error, CommandQueue, Event, BaseEvent, EMPTY};
+use crate::event::WriteBuffer;
+use super::{MemBuffer, MemFlag, DeviceElement, ReadSlice, WriteSlice, DEFAULT_CHUNK_SIZE, io::{seek_position, io_error}};
+
+#[cfg(feature = "error-stack")]
+use alloc::format;
+
+impl<T: DeviceElement> MemBuffer<T> {
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub async fn from_io_async<R: ?Sized + Unpin + AsyncRead> (flags: MemFlag, src: &mut R) -> Result<Self> {
+        Self::from_io_with_context_async(Context::default(), flags, src).await
+    }
+
+    pub async fn from_io_with_context_async<R: ?Sized + Unpin + AsyncRead> (ctx: &Context, flags: MemFlag, src: &mut R) -> Result<Self> {
+        let mut buff = Vec::<u8>::with_capacity(core::mem::size_of::<T>());
+        let read = src.read_to_end(&mut buff).await;
+
+        #[cfg(feature = "error-stack")]
+        read.map_err(|e| error_stack::Report::new(Error::MemObjectAllocationFailure).attach_printable(e))?;
+        #[cfg(not(feature = "error-stack"))]
+        read.map_err(|_| Error::MemObjectAllocationFailure)?;
+
+        if buff.len() % core::mem::size_of::<T>() != 0 {
+            #[cfg(feature = "error-stack")]
+            return Err(error_stack::Report::new(Error::InvalidBufferSize).attach_printable("Buffer size is not a multiple of the element size"));
+            #[cfg(not(feature = "error-stack"))]
+            return Err(Error::InvalidBufferSize);
+        }
+
+        // OpenCL copies the host memory byte by byte, so it doesn't need to be aligned to `T`
+        let len = buff.len() / core::mem::size_of::<T>();
+        unsafe { Self::with_host_ptr(ctx, len, flags | MemFlag::COPY_HOST_PTR, NonNull::new(buff.as_mut_ptr().cast())) }
+    }
+
+    /// Creates a buffer of ```len``` elements and fills it with the bytes of ```src```, one chunk at a time.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub async fn from_reader_sized_async<R: ?Sized + Unpin + AsyncRead> (len: usize, flags: MemFlag, src: &mut R) -> Result<Self> {
+        Self::from_reader_sized_with_queue_async(CommandQueue::default(), len, flags, src).await
+    }
+
+    /// Creates a buffer of ```len``` elements and fills it with the bytes of ```src```, one chunk at a time. The buffer is created on the context of ```queue```.
+    /// # Errors
+    /// Returns [```Error::InvalidBufferSize```] if ```src``` ends before the buffer is filled, and [```Error::MemObjectAllocationFailure```] if reading from ```src``` fails
+    pub async fn from_reader_sized_with_queue_async<R: ?Sized + Unpin + AsyncRead> (queue: &CommandQueue, len: usize, flags: MemFlag, src: &mut R) -> Result<Self> {
+        let ctx = queue.context()?;
+        let flags = flags - (MemFlag::COPY_HOST_PTR | MemFlag::USE_HOST_PTR);
+        let mut this = unsafe { Self::uninit_with_context(&ctx, len, flags)? };
+        let size = this.byte_size()? as u64;
+
+        let mut writer = this.async_writer_with_queue(queue, DEFAULT_CHUNK_SIZE);
+        let copy = match futures::io::copy(src.take(size), &mut writer).await {
+            Ok(n) => writer.close().await.map(|_| n),
+            Err(e) => Err(e)
+        };
+        drop(writer);
+
+        #[cfg(feature = "error-stack")]
+        let copy = copy.map_err(|e| error_stack::Report::new(Error::MemObjectAllocationFailure).attach_printable(e))?;
+        #[cfg(not(feature = "error-stack"))]
+        let copy = copy.map_err(|_| Error::MemObjectAllocationFailure)?;
+
+        if copy != size {
+            #[cfg(feature = "error-stack")]
+            return Err(error_stack::Report::new(Error::InvalidBufferSize).attach_printable(format!("reader ended after {copy} of {size} bytes")));
+            #[cfg(not(feature = "error-stack"))]
+            return Err(Error::InvalidBufferSize);
+        }
+
+        Ok(this)
+    }
+
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub async fn write_into_async<W: ?Sized + Unpin + AsyncWrite> (&self, dst: &mut W, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<()> {
+        self.write_into_with_queue_async(CommandQueue::default(), dst, wait).await
+    }
+
+    pub async fn write_into_with_queue_async<W: ?Sized + Unpin + AsyncWrite> (&self, queue: &CommandQueue, dst: &mut W, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<()> {
+        queue.marker(wait)?.await?;
+        let reader = self.async_reader_with_queue(queue, DEFAULT_CHUNK_SIZE);
+        let write = futures::io::copy(reader, dst).await;
+
+        #[cfg(feature = "error-stack")]
+        write.map_err(|e| error_stack::Report::new(Error::MemObjectAllocationFailure).attach_printable(e))?;
+        #[cfg(not(feature = "error-stack"))]
+        write.map_err(|_| Error::MemObjectAllocationFailure)?;
+
+        Ok(())
+    }
+
+    /// Returns an asynchronous reader over the bytes of the buffer, which transfers ```chunk_size``` bytes at a time from the default queue.
+    /// # Panics
+    /// Panics if ```chunk_size``` is zero
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn async_reader (&self, chunk_size: usize) -> AsyncBufferReader<'_> {
+        self.async_reader_with_queue(CommandQueue::default(), chunk_size)
+    }
+
+    /// Returns an asynchronous reader over the bytes of the buffer, which transfers ```chunk_size``` bytes at a time from ```queue```.
+    /// # Panics
+    /// Panics if ```chunk_size``` is zero
+    #[inline(always)]
+    pub fn async_reader_with_queue<'a> (&'a self, queue: &'a CommandQueue, chunk_size: usize) -> AsyncBufferReader<'a> {
+        AsyncBufferReader::new(queue, self.as_bytes(), chunk_size)
+    }
+
+    /// Returns an asynchronous writer over the bytes of the buffer, which transfers ```chunk_size``` bytes at a time from the default queue.
+    /// # Panics
+    /// Panics if ```chunk_size``` is zero
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn async_writer (&mut self, chunk_size: usize) -> AsyncBufferWriter<'_> {
+        self.async_writer_with_queue(CommandQueue::default(), chunk_size)
+    }
+
+    /// Returns an asynchronous writer over the bytes of the buffer, which transfers ```chunk_size``` bytes at a time from ```queue```.
+    /// # Panics
+    /// Panics if ```chunk_size``` is zero
+    #[inline(always)]
+    pub fn async_writer_with_queue<'a> (&'a mut self, queue: &'a CommandQueue, chunk_size: usize) -> AsyncBufferWriter<'a> {
+        AsyncBufferWriter::new(queue, self.as_bytes_mut(), chunk_size)
+    }
+}
+
+/// Asynchronous reader over the bytes of a [```MemBuffer```].
+///
+/// Data is read from the device in chunks through two staging buffers: while one chunk is being consumed, the next one is already being transferred.
+/// If the reader is dropped while a transfer is in flight, the drop blocks until the transfer has completed.
+pub struct AsyncBufferReader<'a> {
+    queue: &'a CommandQueue,
+    bytes: ReadSlice<'a, u8>,
+    size: u64,
+    /// Chunk being consumed
+    front: Box<[u8]>,
+    /// Position of the first byte of ```front``` inside the buffer
+    offset: u64,
+    start: usize,
+    end: usize,
+    /// Chunk being transferred
+    back: Box<[u8]>,
+    /// Transfer into ```back```, with its position inside the buffer and its length
+    pending: Option<(BaseEvent, u64, usize)>
+}
+
+impl<'a> AsyncBufferReader<'a> {
+    fn new (queue: &'a CommandQueue, bytes: ReadSlice<'a, u8>, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "chunk size must be greater than zero");
+        let size = bytes.byte_size().map_or(0, |x| x as u64);
+
+        Self {
+            queue,
+            bytes,
+            size,
+            front: alloc::vec![0; chunk_size].into_boxed_slice(),
+            offset: 0,
+            start: 0,
+            end: 0,
+            back: alloc::vec![0; chunk_size].into_boxed_slice(),
+            pending: None
+        }
+    }
+
+    #[inline(always)]
+    fn position (&self) -> u64 {
+        self.offset + self.start as u64
+    }
+
+    /// Starts transferring the chunk at ```pos``` into ```back```, if there's no other transfer in flight
+    fn prefetch (&mut self, pos: u64) -> Result<()> {
+        if self.pending.is_some() || pos >= self.size {
+            return Ok(());
+        }
+
+        let len = usize::try_from(self.size - pos).unwrap_or(usize::MAX).min(self.back.len());
+        let start = pos as usize;
+
+        // SAFETY: `back` isn't accessed (nor freed) until the transfer has completed
+        let evt = unsafe { self.bytes.read_into_ptr_with_queue(self.queue, start..start + len, self.back.as_mut_ptr(), EMPTY)? };
+        self.pending = Some((evt.as_ref().clone(), pos, len));
+        Ok(())
+    }
+}
+
+impl AsyncRead for AsyncBufferReader<'_> {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>, buf: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let this = &mut *self;
+
+        loop {
+            if this.start < this.end {
+                let len = buf.len().min(this.end - this.start);
+                buf[..len].copy_from_slice(&this.front[this.start..this.start + len]);
+                this.start += len;
+
+                let next = this.offset + this.end as u64;
+                this.prefetch(next).map_err(io_error)?;
+                return Poll::Ready(Ok(len));
+            }
+
+            let pos = this.position();
+            match this.pending.as_mut() {
+                Some((evt, offset, len)) => {
+                    ready!(Pin::new(evt).poll(cx)).map_err(io_error)?;
+                    let (offset, len) = (*offset, *len);
+                    this.pending = None;
+
+                    // the chunk may be stale after a seek
+                    if offset == pos {
+                        core::mem::swap(&mut this.front, &mut this.back);
+                        this.offset = offset;
+                        this.start = 0;
+                        this.end = len;
+                    }
+                },
+
+                None if pos >= this.size => return Poll::Ready(Ok(0)),
+                None => this.prefetch(pos).map_err(io_error)?
+            }
+        }
+    }
+}
+
+impl AsyncSeek for AsyncBufferReader<'_> {
+    fn poll_seek(mut self: Pin<&mut Self>, _cx: &mut TaskContext<'_>, pos: SeekFrom) -> Poll<std::io::Result<u64>> {
+        let this = &mut *self;
+        let pos = seek_position(pos, this.position(), this.size)?;
+
+        // keep the staged chunk if the new position is inside of it
+        match pos.checked_sub(this.offset) {
+            Some(delta) if delta <= this.end as u64 => this.start = delta as usize,
+            _ => {
+                this.offset = pos;
+                this.start = 0;
+                this.end = 0;
+            }
+        }
+
+        Poll::Ready(Ok(pos))
+    }
+}
+
+impl Drop for AsyncBufferReader<'_> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        if let Some((evt, _, _)) = self.pending.take() {
+            let _ = evt.wait();
+        }
+    }
+}
+
+/// Asynchronous writer over the bytes of a [```MemBuffer```].
+///
+/// Data is written to the device in chunks through two staging buffers: while one chunk is being transferred, the next one is already being filled.
+/// Writes past the end of the buffer return ```Ok(0)```. Like [```BufferWriter```](super::BufferWriter), any pending data is written when the writer is dropped, blocking until the transfer has completed,
+/// but errors are ignored, so [```close```](AsyncWriteExt::close) should be called first.
+pub struct AsyncBufferWriter<'a> {
+    queue: &'a CommandQueue,
+    bytes: WriteSlice<'a, u8>,
+    size: u64,
+    chunk_size: usize,
+    /// Chunk being filled
+    front: Vec<u8>,
+    /// Position of the first byte of ```front``` inside the buffer
+    offset: u64,
+    /// Chunk being transferred
+    back: Vec<u8>,
+    pending: Option<BaseEvent>
+}
+
+impl<'a> AsyncBufferWriter<'a> {
+    fn new (queue: &'a CommandQueue, bytes: WriteSlice<'a, u8>, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "chunk size must be greater than zero");
+        let size = bytes.byte_size().map_or(0, |x| x as u64);
+
+        Self {
+            queue,
+            bytes,
+            size,
+            chunk_size,
+            front: Vec::with_capacity(chunk_size),
+            offset: 0,
+            back: Vec::with_capacity(chunk_size),
+            pending: None
+        }
+    }
+
+    #[inline(always)]
+    fn position (&self) -> u64 {
+        self.offset + self.front.len() as u64
+    }
+
+    /// Waits for the transfer in flight (if any) to complete
+    fn poll_pending (&mut self, cx: &mut TaskContext<'_>) -> Poll<std::io::Result<()>> {
+        if let Some(evt) = self.pending.as_mut() {
+            ready!(Pin::new(evt).poll(cx)).map_err(io_error)?;
+            self.pending = None;
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    /// Starts transferring ```front``` to the device, once the previous transfer has completed
+    fn poll_submit (&mut self, cx: &mut TaskContext<'_>) -> Poll<std::io::Result<()>> {
+        if self.front.is_empty() {
+            return Poll::Ready(Ok(()));
+        }
+
+        ready!(self.poll_pending(cx))?;
+        core::mem::swap(&mut self.front, &mut self.back);
+        self.front.clear();
+
+        // SAFETY: `back` isn't accessed (nor freed) until the transfer has completed
+        let src = unsafe { core::slice::from_raw_parts(self.back.as_ptr(), self.back.len()) };
+        let evt = unsafe { WriteBuffer::new_by_ref(self.queue, false, self.offset as usize, src, &self.bytes, EMPTY) }.map_err(io_error)?;
+
+        self.pending = Some(evt.as_ref().clone());
+        self.offset += self.back.len() as u64;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncWrite for AsyncBufferWriter<'_> {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        let this = &mut *self;
+        if this.front.len() == this.chunk_size {
+            ready!(this.poll_submit(cx))?;
+        }
+
+        let remaining = usize::try_from(this.size.saturating_sub(this.position())).unwrap_or(usize::MAX);
+        let len = buf.len().min(remaining).min(this.chunk_size - this.front.len());
+        this.front.extend_from_slice(&buf[..len]);
+        Poll::Ready(Ok(len))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<std::io::Result<()>> {
+        let this = &mut *self;
+        ready!(this.poll_submit(cx))?;
+        this.poll_pending(cx)
+    }
+
+    #[inline(always)]
+    fn poll_close(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<std::io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+impl AsyncSeek for AsyncBufferWriter<'_> {
+    fn poll_seek(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>, pos: SeekFrom) -> Poll<std::io::Result<u64>> {
+        let pos = seek_position(pos, self.position(), self.size)?;
+        ready!(self.as_mut().poll_flush(cx))?;
+        self.offset = pos;
+        Poll::Ready(Ok(pos))
+    }
+}
+
+impl Drop for AsyncBufferWriter<'_> {
+    fn drop(&mut self) {
+        if let Some(evt) = self.pending.take() {
+            let _ = evt.wait();
+        }
+
+        if !self.front.is_empty() {
+            // SAFETY: the write is blocking, so `front` isn't freed until it has completed
+            let _ = unsafe { WriteBuffer::new_by_ref(self.queue, true, self.offset as usize, &self.front, &self.bytes, EMPTY) };
+        }
+    }
+}
@@ -1,16 +1,18 @@
-use core::{ptr::{NonNull}, marker::PhantomData, mem::{MaybeUninit, ManuallyDrop}, ops::{RangeBounds, Bound}, fmt::Debug, ffi::c_void};
+use core::{ptr::{NonNull, addr_of}, marker::PhantomData, mem::{MaybeUninit, ManuallyDrop}, ops::{RangeBounds, Bound}, fmt::Debug, ffi::c_void};
 use alloc::{vec::{Vec, IntoIter}, boxed::Box};
-use opencl_sys::{cl_mem, clReleaseMemObject, clCreateBuffer, cl_mem_info, clGetMemObjectInfo, CL_MEM_FLAGS, CL_MEM_SIZE, CL_MEM_HOST_PTR, CL_MEM_MAP_COUNT, CL_MEM_REFERENCE_COUNT, CL_MEM_CONTEXT, CL_MEM_OFFSET, clRetainContext};
+use opencl_sys::{cl_mem, clReleaseMemObject, clCreateBuffer, cl_mem_info, clGetMemObjectInfo, CL_MEM_FLAGS, CL_MEM_SIZE, CL_MEM_HOST_PTR, CL_MEM_MAP_COUNT, CL_MEM_REFERENCE_COUNT, CL_MEM_CONTEXT, CL_MEM_OFFSET, CL_MEM_TYPE, CL_MEM_ASSOCIATED_MEMOBJECT, CL_BUFFER_CREATE_TYPE_REGION, clRetainContext, clRetainMemObject, clCreateSubBuffer, clSetMemObjectDestructorCallback};
 use parking_lot::RawRwLock;
-use crate::{prelude::{Result, Context, Error, CommandQueue, EMPTY}, event::{ReadBuffer, BaseEvent, WriteBuffer, Event, CopyBuffer, various::{Then, Map}}};
-use super::{MemFlag};
+use crate::{prelude::{Result, Context, Error, CommandQueue, EMPTY}, event::{ReadBuffer, BaseEvent, WriteBuffer, Event, CopyBuffer, FillBuffer, MapBuffer, MapBufferMut, ReadBufferRect, WriteBufferRect, CopyBufferRect, various::{Then, Map, Swap}}};
+use super::{DeviceElement, MemFlag, MemMigrationFlag, MemObjectType, MemBufferInfo, ReadSlice, WriteSlice, RectLayout};
+#[cfg(doc)]
+use super::{MapGuard, MapMutGuard};
 
 #[cfg(feature = "error-stack")]
 use alloc::format;
 
-pub struct MemBuffer<T: 'static + Copy + Unpin> (pub(crate) cl_mem, pub(crate) RawRwLock, pub(super) PhantomData<T>); 
+pub struct MemBuffer<T: DeviceElement> (pub(crate) cl_mem, pub(crate) RawRwLock, pub(super) PhantomData<T>); 
 
-impl<T: Copy + Unpin> MemBuffer<T> {
+impl<T: DeviceElement> MemBuffer<T> {
     #[cfg(feature = "def")]
     #[inline(always)]
     pub unsafe fn uninit (size: usize, flags: MemFlag) -> Result<Self> {
@@ -34,6 +36,53 @@ impl<T: Copy + Unpin> MemBuffer<T> {
         unsafe { Self::with_host_ptr(ctx, src.len(), flags, NonNull::new(src.as_ptr() as *mut _)) }
     }
 
+    /// Creates a buffer that uses the memory of ```src``` as its storage (i.e. [```MemFlag::USE_HOST_PTR```]). The vector is freed once OpenCL releases the buffer.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn from_vec (src: Vec<T>, flags: MemFlag) -> Result<Self> where T: Send {
+        Self::from_vec_with_context(Context::default(), src, flags)
+    }
+
+    /// Creates a buffer that uses the memory of ```src``` as its storage (i.e. [```MemFlag::USE_HOST_PTR```]). The vector is freed once OpenCL releases the buffer.
+    #[inline(always)]
+    pub fn from_vec_with_context (ctx: &Context, src: Vec<T>, flags: MemFlag) -> Result<Self> where T: Send {
+        Self::from_box_with_context(ctx, src.into_boxed_slice(), flags)
+    }
+
+    /// Creates a buffer that uses the memory of ```src``` as its storage (i.e. [```MemFlag::USE_HOST_PTR```]). The allocation is freed once OpenCL releases the buffer.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn from_box (src: Box<[T]>, flags: MemFlag) -> Result<Self> where T: Send {
+        Self::from_box_with_context(Context::default(), src, flags)
+    }
+
+    /// Creates a buffer that uses the memory of ```src``` as its storage (i.e. [```MemFlag::USE_HOST_PTR```]). The allocation is freed once OpenCL releases the buffer.
+    /// 
+    /// If the destructor callback can't be registered, the allocation is leaked, since OpenCL may still be using it.
+    pub fn from_box_with_context (ctx: &Context, src: Box<[T]>, flags: MemFlag) -> Result<Self> where T: Send {
+        struct HostAlloc<T> (NonNull<[T]>);
+        unsafe impl<T: Send> Send for HostAlloc<T> {}
+
+        let len = src.len();
+        let alloc = HostAlloc(NonNull::from(Box::leak(src)));
+        let flags = (flags - MemFlag::COPY_HOST_PTR) | MemFlag::USE_HOST_PTR;
+        
+        let this = match unsafe { Self::with_host_ptr(ctx, len, flags, Some(alloc.0.cast())) } {
+            Ok(x) => x,
+            Err(e) => {
+                drop(unsafe { Box::from_raw(alloc.0.as_ptr()) });
+                return Err(e);
+            }
+        };
+
+        this.on_destroy(move || {
+            let alloc = alloc;
+            drop(unsafe { Box::from_raw(alloc.0.as_ptr()) })
+        })?;
+
+        Ok(this)
+    }
+
     pub unsafe fn with_host_ptr (ctx: &Context, size: usize, flags: MemFlag, host_ptr: Option<NonNull<T>>) -> Result<Self> {
         let host_ptr = match host_ptr {
             Some(x) => x.as_ptr().cast(),
@@ -70,9 +119,10 @@ impl<T: Copy + Unpin> MemBuffer<T> {
         }
     }
 
+    /// Returns the type of the memory object. For buffers and sub-buffers this is always [```MemObjectType::Buffer```].
     #[inline(always)]
-    pub fn ty (&self) -> Result<()> {
-        todo!()
+    pub fn ty (&self) -> Result<MemObjectType> {
+        self.get_info(CL_MEM_TYPE)
     }
 
     /// Returns the flags argument value specified when memobj is created
@@ -117,25 +167,146 @@ impl<T: Copy + Unpin> MemBuffer<T> {
         Ok(ctx)
     }
 
-    /*
-    /// Return memory object from which memobj is created. 
+    /// Return a read-only view of the memory object from which memobj is created, or ```None``` if this buffer isn't a sub-buffer.
+    /// The view borrows this buffer, so the parent can't be modified through it while this buffer (or the slice it belongs to) is borrowed.
     #[inline(always)]
-    pub fn parent (&self) -> Result<Option<MemBuffer<T>>> {
+    pub fn parent (&self) -> Result<Option<ReadSlice<'_, T>>> {
         let id = self.get_info::<cl_mem>(CL_MEM_ASSOCIATED_MEMOBJECT)?;
         if id.is_null() { return Ok(None); }
-        Ok(Some(MemBuffer(id, PhantomData)))
-    }*/
 
+        unsafe {
+            tri_panic!(clRetainMemObject(id));
+            Ok(Some(ReadSlice::from_id(id)))
+        }
+    }
+
+    /// Return offset (in bytes) if this buffer is a sub-buffer, or 0 otherwise.
     #[inline(always)]
     pub fn offset (&self) -> Result<usize> {
         self.get_info(CL_MEM_OFFSET)
     }
 
+    /// Returns ```true``` if the buffer was created with a host pointer that was allocated as a shared virtual memory region.
+    #[cfg(feature = "cl2")]
+    #[inline(always)]
+    pub fn uses_svm_pointer (&self) -> Result<bool> {
+        let v = self.get_info::<opencl_sys::cl_bool>(opencl_sys::CL_MEM_USES_SVM_POINTER)?;
+        Ok(v != 0)
+    }
+
+    /// Registers a callback that is called once OpenCL deletes the buffer (i.e. after it's dropped and every command that uses it has completed). Callbacks are called in the reverse order of their registration.
+    /// 
+    /// The callback may be called from a thread owned by the OpenCL implementation, so it shouldn't call any blocking OpenCL function.
+    pub fn on_destroy (&self, f: impl 'static + Send + FnOnce()) -> Result<()> {
+        let f = Box::into_raw(Box::new(Box::new(f) as Box<dyn Send + FnOnce()>));
+        let err = unsafe { clSetMemObjectDestructorCallback(self.0, Some(destructor_callback), f.cast()) };
+
+        if err == 0 {
+            return Ok(());
+        }
+
+        drop(unsafe { Box::from_raw(f) });
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "error-stack")] {
+                let err = Error::from(err);
+                let report = error_stack::Report::new(err);
+
+                let report = match err {
+                    Error::InvalidMemObject => report.attach_printable(format!("'{:?}' is not a valid memory object", self.0)),
+                    Error::OutOfResources => report.attach_printable("failure to allocate resources required by the OpenCL implementation on the device"),
+                    Error::OutOfHostMemory => report.attach_printable("failure to allocate resources required by the OpenCL implementation on the host"),
+                    _ => report
+                };
+
+                Err(report)
+            } else {
+                Err(Error::from(err))
+            }
+        }
+    }
+
+    /// Returns a snapshot of the buffer's properties, including those of its parent if it's a sub-buffer.
+    pub fn info (&self) -> Result<MemBufferInfo> {
+        let parent = match self.parent()? {
+            Some(parent) => Some(Box::new(parent.info()?)),
+            None => None
+        };
+
+        Ok(MemBufferInfo {
+            id: self.0,
+            ty: self.ty()?,
+            flags: self.flags()?,
+            size: self.byte_size()?,
+            len: self.len()?,
+            host_ptr: self.host_ptr()?,
+            map_count: self.map_count()?,
+            reference_count: self.reference_count()?,
+            offset: self.offset()?,
+            #[cfg(feature = "cl2")]
+            uses_svm_pointer: self.uses_svm_pointer()?,
+            parent
+        })
+    }
+
+    /// Creates a new buffer with the same context, flags and contents as this one. The contents are copied on the device.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn try_clone (&self, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Swap<MemBuffer<T>, CopyBuffer<'_, 'static>>> {
+        self.try_clone_with_queue(CommandQueue::default(), wait)
+    }
+
+    /// Creates a new buffer with the same context, flags and contents as this one. The contents are copied on the device.
+    /// 
+    /// [```MemFlag::USE_HOST_PTR```] and [```MemFlag::COPY_HOST_PTR```] aren't carried over to the new buffer, since it isn't backed by any host memory.
+    pub fn try_clone_with_queue (&self, queue: &CommandQueue, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Swap<MemBuffer<T>, CopyBuffer<'_, 'static>>> {
+        let flags = self.flags()? - (MemFlag::USE_HOST_PTR | MemFlag::COPY_HOST_PTR);
+        let len = self.len()?;
+
+        let ctx = self.context()?;
+        let new = unsafe { Self::uninit_with_context(&ctx, len, flags)? };
+        // SAFETY: the new buffer can't be accessed until the event has completed
+        let evt = unsafe { CopyBuffer::new_by_ref(queue, 0, 0, len, self, &new, wait)? };
+        Ok(Swap::new(evt, new))
+    }
+
+    /// Reinterprets the buffer as a buffer of ```O```, without any checks. Prefer [```cast```](MemBuffer::cast).
+    /// # Safety
+    /// The size of ```O``` must be equal to the size of ```T```
     #[inline(always)]
-    pub unsafe fn transmute<O: Copy + Unpin> (self) -> MemBuffer<O> {
+    pub unsafe fn transmute<O: DeviceElement> (self) -> MemBuffer<O> {
         debug_assert_eq!(core::mem::size_of::<T>(), core::mem::size_of::<O>());
         let me = ManuallyDrop::new(self);
-        MemBuffer(me.0, me.1, PhantomData)
+        MemBuffer(me.0, parking_lot::lock_api::RawRwLock::INIT, PhantomData)
+    }
+
+    /// Reinterprets the buffer as a buffer of ```U```. The size of ```U``` doesn't need to match the size of ```T```, as long as the buffer's size in bytes is a multiple of it.
+    /// # Errors
+    /// Returns [```Error::InvalidBufferSize```] if the buffer's size in bytes isn't a multiple of the size of ```U```, and [```Error::MisalignedSubBufferOffset```] if the buffer is a sub-buffer whose offset isn't aligned to ```U```.
+    #[inline(always)]
+    pub fn cast<U: DeviceElement> (self) -> Result<MemBuffer<U>> {
+        self.check_cast::<U>()?;
+        let me = ManuallyDrop::new(self);
+        Ok(MemBuffer(me.0, parking_lot::lock_api::RawRwLock::INIT, PhantomData))
+    }
+
+    /// Returns a view of the buffer reinterpreted as a buffer of ```U```. See [```cast```](MemBuffer::cast).
+    #[inline(always)]
+    pub fn cast_ref<U: DeviceElement> (&self) -> Result<ReadSlice<'_, U>> {
+        self.check_cast::<U>()?;
+        unsafe {
+            tri_panic!(clRetainMemObject(self.0));
+            Ok(ReadSlice::from_id(self.0))
+        }
+    }
+
+    /// Returns a mutable view of the buffer reinterpreted as a buffer of ```U```. See [```cast```](MemBuffer::cast).
+    #[inline(always)]
+    pub fn cast_mut<U: DeviceElement> (&mut self) -> Result<WriteSlice<'_, U>> {
+        self.check_cast::<U>()?;
+        unsafe {
+            tri_panic!(clRetainMemObject(self.0));
+            Ok(WriteSlice::from_id(self.0))
+        }
     }
 
     #[cfg(feature = "def")]
@@ -176,56 +347,149 @@ impl<T: Copy + Unpin> MemBuffer<T> {
         Ok(evt.then(move |_| unsafe { drop(Box::from_raw(ptr)) }))
     }
 
-    /*pub fn slice (&self, range: impl RangeBounds<usize>) -> Result<Self> {
-        let (offset, len) = self.get_offset_len(&range)?;
-        let flags = self.flags()? & (MemFlag::READ_WRITE | MemFlag::READ_ONLY | MemFlag::WRITE_ONLY);
-        let offset = offset.checked_mul(core::mem::size_of::<T>()).expect("Integer overflow. Too many elements in buffer");
-        let len = len.checked_mul(core::mem::size_of::<T>()).expect("Integer overflow. Too many elements in buffer");
+    /// Returns a read-only view of the elements inside ```range```, backed by an OpenCL sub-buffer.
+    /// # Errors
+    /// Returns [```Error::MisalignedSubBufferOffset```] if the start of the range isn't aligned to the [```mem_base_addr_align```](crate::device::Device::mem_base_addr_align) of every device in the buffer's context.
+    #[inline(always)]
+    pub fn slice (&self, range: impl RangeBounds<usize>) -> Result<ReadSlice<'_, T>> {
+        let id = self.create_sub_buffer(&range)?;
+        Ok(unsafe { ReadSlice::from_id(id) })
+    }
 
-        let region = opencl_sys::cl_buffer_region {
-            origin: offset,
-            size: len
-        };
-        
-        let mut err = 0;
-        let id = unsafe { clCreateSubBuffer(self.0, flags.bits(), CL_BUFFER_CREATE_TYPE_REGION, addr_of!(region).cast(), &mut err) };
+    /// Returns a mutable view of the elements inside ```range```, backed by an OpenCL sub-buffer.
+    /// # Errors
+    /// Returns [```Error::MisalignedSubBufferOffset```] if the start of the range isn't aligned to the [```mem_base_addr_align```](crate::device::Device::mem_base_addr_align) of every device in the buffer's context.
+    #[inline(always)]
+    pub fn slice_mut (&mut self, range: impl RangeBounds<usize>) -> Result<WriteSlice<'_, T>> {
+        let id = self.create_sub_buffer(&range)?;
+        Ok(unsafe { WriteSlice::from_id(id) })
+    }
 
-        if err == 0 {
-            return Ok(Self(id, PhantomData));
+    /// Returns a view of the buffer's raw bytes
+    #[inline(always)]
+    pub fn as_bytes (&self) -> ReadSlice<'_, u8> {
+        unsafe {
+            tri_panic!(clRetainMemObject(self.0));
+            ReadSlice::from_id(self.0)
         }
+    }
 
-        cfg_if::cfg_if! {
-            if #[cfg(feature = "error-stack")] {
-                let err = Error::from(err);
-                let report = error_stack::Report::new(err);
+    /// Returns a mutable view of the buffer's raw bytes
+    #[inline(always)]
+    pub fn as_bytes_mut (&mut self) -> WriteSlice<'_, u8> {
+        unsafe {
+            tri_panic!(clRetainMemObject(self.0));
+            WriteSlice::from_id(self.0)
+        }
+    }
 
-                let report = match err {
-                    Error::InvalidValue => report.attach_printable(format!("'{:?}' is not a valid flag", flags)),
-                    Error::InvalidMemObject => report.attach_printable(format!("'{:?}' is not a valid memory object", self.0)),
-                    Error::OutOfHostMemory => report.attach_printable("failure to allocate resources required by the OpenCL implementation on the host"),
-                    Error::OutOfResources => report.attach_printable("failure to allocate resources required by the OpenCL implementation on the device"),
-                    _ => report
-                };
+    /// Maps the elements inside ```range``` into host memory for reading. The region is unmapped when the resulting [```MapGuard```] is dropped.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn map (&self, range: impl RangeBounds<usize>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<MapBuffer<'_, T>> {
+        self.map_with_queue(CommandQueue::default(), range, wait)
+    }
 
-                Err(report)
-            } else {
-                Err(Error::from(err))
-            }
-        }
-    }*/
+    /// Maps the elements inside ```range``` into host memory for reading. The region is unmapped when the resulting [```MapGuard```] is dropped.
+    #[inline(always)]
+    pub fn map_with_queue (&self, queue: &CommandQueue, range: impl RangeBounds<usize>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<MapBuffer<'_, T>> {
+        let (offset, len) = self.get_offset_len(&range)?;
+        MapBuffer::new(queue, offset, len, self, wait)
+    }
+
+    /// Maps the elements inside ```range``` into host memory for reading and writing. The region is unmapped when the resulting [```MapMutGuard```] is dropped.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn map_mut (&mut self, range: impl RangeBounds<usize>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<MapBufferMut<'_, T>> {
+        self.map_mut_with_queue(CommandQueue::default(), range, wait)
+    }
 
+    /// Maps the elements inside ```range``` into host memory for reading and writing. The region is unmapped when the resulting [```MapMutGuard```] is dropped.
+    #[inline(always)]
+    pub fn map_mut_with_queue (&mut self, queue: &CommandQueue, range: impl RangeBounds<usize>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<MapBufferMut<'_, T>> {
+        let (offset, len) = self.get_offset_len(&range)?;
+        MapBufferMut::new(queue, offset, len, self, wait)
+    }
+
+    /// Copies the elements of this buffer, starting at ```offset```, into the elements of ```dst``` inside ```dst_range```.
     #[cfg(feature = "def")]
     #[inline(always)]
     pub fn copy_to<'a> (&self, offset: usize, dst: &'a mut MemBuffer<T>, dst_range: impl RangeBounds<usize>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<CopyBuffer<'_, 'a>> {
         self.copy_to_with_queue(CommandQueue::default(), offset, dst, dst_range, wait)
     }
 
+    /// Copies the elements of this buffer, starting at ```offset```, into the elements of ```dst``` inside ```dst_range```.
+    /// # Errors
+    /// Returns [```Error::InvalidValue```] if this buffer has less than ```offset + dst_range.len()``` elements
     #[inline(always)]
     pub fn copy_to_with_queue<'a, 'b> (&'a self, queue: &CommandQueue, offset: usize, dst: &'b mut MemBuffer<T>, dst_range: impl RangeBounds<usize>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<CopyBuffer<'a, 'b>> {
-        let (dst_offset, len) = self.get_offset_len(&dst_range)?;
+        let (dst_offset, len) = dst.get_offset_len(&dst_range)?;
+        let total = self.len()?;
+
+        if offset.checked_add(len).map_or(true, |end| end > total) {
+            #[cfg(feature = "error-stack")]
+            return Err(error_stack::Report::new(Error::InvalidValue).attach_printable(format!("range {offset}..{} is out of bounds for a buffer of length {total}", offset + len)));
+            #[cfg(not(feature = "error-stack"))]
+            return Err(Error::InvalidValue);
+        }
+
         CopyBuffer::new(queue, offset, dst_offset, len, self, dst, wait)
     }
 
+    /// Sets every element inside ```range``` to ```v```.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn fill (&mut self, v: T, range: impl RangeBounds<usize>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<FillBuffer<'_>> {
+        self.fill_with_queue(CommandQueue::default(), v, range, wait)
+    }
+
+    /// Sets every element inside ```range``` to ```v```.
+    #[inline(always)]
+    pub fn fill_with_queue (&mut self, queue: &CommandQueue, v: T, range: impl RangeBounds<usize>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<FillBuffer<'_>> {
+        self.fill_pattern_with_queue(queue, core::slice::from_ref(&v), range, wait)
+    }
+
+    /// Fills the elements inside ```range``` with repeated copies of ```pattern```.
+    /// # Errors
+    /// Returns [```Error::InvalidValue```] if the range is out of bounds, if the byte size of ```pattern``` isn't a power of two no larger than 128, or if the length of the range isn't a multiple of the pattern's length.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn fill_pattern (&mut self, pattern: &[T], range: impl RangeBounds<usize>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<FillBuffer<'_>> {
+        self.fill_pattern_with_queue(CommandQueue::default(), pattern, range, wait)
+    }
+
+    /// Fills the elements inside ```range``` with repeated copies of ```pattern```.
+    /// # Errors
+    /// Returns [```Error::InvalidValue```] if the range is out of bounds, if the byte size of ```pattern``` isn't a power of two no larger than 128, or if the length of the range isn't a multiple of the pattern's length.
+    pub fn fill_pattern_with_queue (&mut self, queue: &CommandQueue, pattern: &[T], range: impl RangeBounds<usize>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<FillBuffer<'_>> {
+        let (offset, len) = self.get_offset_len(&range)?;
+        let total = self.len()?;
+        
+        if offset.checked_add(len).map_or(true, |end| end > total) {
+            #[cfg(feature = "error-stack")]
+            return Err(error_stack::Report::new(Error::InvalidValue).attach_printable(format!("range {offset}..{} is out of bounds for a buffer of length {total}", offset + len)));
+            #[cfg(not(feature = "error-stack"))]
+            return Err(Error::InvalidValue);
+        }
+
+        let pattern_size = core::mem::size_of_val(pattern);
+        if !pattern_size.is_power_of_two() || pattern_size > 128 {
+            #[cfg(feature = "error-stack")]
+            return Err(error_stack::Report::new(Error::InvalidValue).attach_printable(format!("pattern size must be a power of two no larger than 128 bytes, found {pattern_size} bytes")));
+            #[cfg(not(feature = "error-stack"))]
+            return Err(Error::InvalidValue);
+        }
+
+        if len % pattern.len() != 0 {
+            #[cfg(feature = "error-stack")]
+            return Err(error_stack::Report::new(Error::InvalidValue).attach_printable(format!("range length ({len}) must be a multiple of the pattern length ({})", pattern.len())));
+            #[cfg(not(feature = "error-stack"))]
+            return Err(Error::InvalidValue);
+        }
+
+        FillBuffer::new(queue, pattern, offset, len, self, wait)
+    }
+
     #[cfg(feature = "def")]
     #[inline(always)]
     pub fn read (&self, range: impl RangeBounds<usize>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Map<Vec<T>, ReadBuffer<'_, 'static>, impl FnOnce(()) -> Vec<T>>> {
@@ -286,6 +550,51 @@ impl<T: Copy + Unpin> MemBuffer<T> {
         WriteBuffer::new(queue, false, offset, src, self, wait)
     }
 
+    /// Reads the ```region``` (```[columns, rows, slices]```, in elements) located at ```layout``` inside the buffer into ```dst_layout``` inside ```dst```.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn read_rect<'a> (&self, layout: RectLayout, dst: &'a mut [T], dst_layout: RectLayout, region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<ReadBufferRect<'_, 'a>> {
+        self.read_rect_with_queue(CommandQueue::default(), layout, dst, dst_layout, region, wait)
+    }
+
+    /// Reads the ```region``` (```[columns, rows, slices]```, in elements) located at ```layout``` inside the buffer into ```dst_layout``` inside ```dst```.
+    #[inline(always)]
+    pub fn read_rect_with_queue<'a> (&self, queue: &CommandQueue, layout: RectLayout, dst: &'a mut [T], dst_layout: RectLayout, region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<ReadBufferRect<'_, 'a>> {
+        ReadBufferRect::new(queue, false, self, layout, dst, dst_layout, region, wait)
+    }
+
+    /// Writes the ```region``` (```[columns, rows, slices]```, in elements) located at ```src_layout``` inside ```src``` into ```layout``` inside the buffer.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn write_rect<'a> (&mut self, layout: RectLayout, src: &'a [T], src_layout: RectLayout, region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<WriteBufferRect<'a, '_>> {
+        self.write_rect_with_queue(CommandQueue::default(), layout, src, src_layout, region, wait)
+    }
+
+    /// Writes the ```region``` (```[columns, rows, slices]```, in elements) located at ```src_layout``` inside ```src``` into ```layout``` inside the buffer.
+    #[inline(always)]
+    pub fn write_rect_with_queue<'a> (&mut self, queue: &CommandQueue, layout: RectLayout, src: &'a [T], src_layout: RectLayout, region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<WriteBufferRect<'a, '_>> {
+        WriteBufferRect::new(queue, false, src, src_layout, self, layout, region, wait)
+    }
+
+    /// Copies the ```region``` (```[columns, rows, slices]```, in elements) located at ```layout``` inside the buffer into ```dst_layout``` inside ```dst```.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn copy_rect_to<'a> (&self, layout: RectLayout, dst: &'a mut MemBuffer<T>, dst_layout: RectLayout, region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<CopyBufferRect<'_, 'a>> {
+        self.copy_rect_to_with_queue(CommandQueue::default(), layout, dst, dst_layout, region, wait)
+    }
+
+    /// Copies the ```region``` (```[columns, rows, slices]```, in elements) located at ```layout``` inside the buffer into ```dst_layout``` inside ```dst```.
+    #[inline(always)]
+    pub fn copy_rect_to_with_queue<'a> (&self, queue: &CommandQueue, layout: RectLayout, dst: &'a mut MemBuffer<T>, dst_layout: RectLayout, region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<CopyBufferRect<'_, 'a>> {
+        CopyBufferRect::new(queue, self, layout, dst, dst_layout, region, wait)
+    }
+
+    /// Migrates the buffer to the device associated with ```queue``` (or to the host, if [```MemMigrationFlag::HOST```] is set), so that it's already resident there when the next command that uses it is executed.
+    #[inline(always)]
+    pub fn migrate_to (&self, queue: &CommandQueue, flags: MemMigrationFlag, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        queue.migrate_all(&[self], flags, wait)
+    }
+
     #[cfg(feature = "def")]
     #[inline(always)]
     pub fn iter (&self, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<IntoIter<T>> {
@@ -339,6 +648,28 @@ impl<T: Copy + Unpin> MemBuffer<T> {
     }
 
     #[inline(always)]
+    fn check_cast<U: DeviceElement> (&self) -> Result<()> {
+        let size = self.byte_size()?;
+        let elem_size = core::mem::size_of::<U>();
+
+        if elem_size == 0 || size % elem_size != 0 {
+            #[cfg(feature = "error-stack")]
+            return Err(error_stack::Report::new(Error::InvalidBufferSize).attach_printable(format!("a buffer of {size} bytes can't be reinterpreted as a buffer of '{}' ({elem_size} bytes)", core::any::type_name::<U>())));
+            #[cfg(not(feature = "error-stack"))]
+            return Err(Error::InvalidBufferSize);
+        }
+
+        let offset = self.offset()?;
+        if offset % core::mem::align_of::<U>() != 0 {
+            #[cfg(feature = "error-stack")]
+            return Err(error_stack::Report::new(Error::MisalignedSubBufferOffset).attach_printable(format!("sub-buffer offset {offset} isn't aligned to '{}'", core::any::type_name::<U>())));
+            #[cfg(not(feature = "error-stack"))]
+            return Err(Error::MisalignedSubBufferOffset);
+        }
+
+        Ok(())
+    }
+
     fn get_offset_len (&self, range: &impl RangeBounds<usize>) -> Result<(usize, usize)> {
         let offset = match range.start_bound() {
             Bound::Included(x) => *x,
@@ -354,10 +685,93 @@ impl<T: Copy + Unpin> MemBuffer<T> {
 
         Ok((offset, len))
     }
+
+    fn create_sub_buffer (&self, range: &impl RangeBounds<usize>) -> Result<cl_mem> {
+        let (offset, len) = self.get_offset_len(range)?;
+        let total = self.len()?;
+
+        if offset.checked_add(len).map_or(true, |end| end > total) {
+            #[cfg(feature = "error-stack")]
+            return Err(error_stack::Report::new(Error::InvalidValue).attach_printable(format!("range {offset}..{} is out of bounds", offset + len)));
+            #[cfg(not(feature = "error-stack"))]
+            return Err(Error::InvalidValue);
+        }
+
+        let mut offset = offset.checked_mul(core::mem::size_of::<T>()).expect("Integer overflow. Too many elements in buffer");
+        let len = len.checked_mul(core::mem::size_of::<T>()).expect("Integer overflow. Too many elements in buffer");
+
+        // OpenCL doesn't allow sub-buffers of sub-buffers, so we slice the parent instead
+        let mut parent = self.0;
+        let associated = self.get_info::<cl_mem>(CL_MEM_ASSOCIATED_MEMOBJECT)?;
+        if !associated.is_null() {
+            offset += self.offset()?;
+            parent = associated;
+        }
+
+        for device in self.context()?.devices()? {
+            let align = usize::try_from(device.mem_base_addr_align()? / 8).unwrap();
+            if align > 0 && offset % align != 0 {
+                #[cfg(feature = "error-stack")]
+                return Err(error_stack::Report::new(Error::MisalignedSubBufferOffset).attach_printable(format!("offset of {offset} bytes is not aligned to {align} bytes")));
+                #[cfg(not(feature = "error-stack"))]
+                return Err(Error::MisalignedSubBufferOffset);
+            }
+        }
+
+        let flags = self.flags()? & (MemFlag::READ_WRITE | MemFlag::READ_ONLY | MemFlag::WRITE_ONLY);
+        let region = opencl_sys::cl_buffer_region {
+            origin: offset,
+            size: len
+        };
+        
+        let mut err = 0;
+        let id = unsafe { clCreateSubBuffer(parent, flags.bits(), CL_BUFFER_CREATE_TYPE_REGION, addr_of!(region).cast(), &mut err) };
+
+        if err == 0 {
+            return Ok(id);
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "error-stack")] {
+                let err = Error::from(err);
+                let report = error_stack::Report::new(err);
+
+                let report = match err {
+                    Error::InvalidValue => report.attach_printable(format!("'{:?}' is not a valid flag or the region is out of bounds", flags)),
+                    Error::InvalidMemObject => report.attach_printable(format!("'{:?}' is not a valid memory object", parent)),
+                    Error::MisalignedSubBufferOffset => report.attach_printable("offset is not aligned to the base address alignment of the devices"),
+                    Error::InvalidBufferSize => report.attach_printable("size is zero"),
+                    Error::OutOfHostMemory => report.attach_printable("failure to allocate resources required by the OpenCL implementation on the host"),
+                    Error::OutOfResources => report.attach_printable("failure to allocate resources required by the OpenCL implementation on the device"),
+                    _ => report
+                };
+
+                Err(report)
+            } else {
+                Err(Error::from(err))
+            }
+        }
+    }
+}
+
+unsafe extern "C" fn destructor_callback (_memobj: cl_mem, user_data: *mut c_void) {
+    let f = Box::from_raw(user_data as *mut Box<dyn Send + FnOnce()>);
+    f()
+}
+
+/// Clones the buffer on the device, blocking until the copy has completed.
+/// # Panics
+/// Panics if the buffer couldn't be cloned. See [```MemBuffer::try_clone```] for a non-panicking version
+#[cfg(feature = "def")]
+impl<T: DeviceElement> Clone for MemBuffer<T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        self.try_clone(EMPTY).and_then(Event::wait).unwrap()
+    }
 }
 
 #[cfg(feature = "def")]
-impl<T: Copy + Unpin + Debug> Debug for MemBuffer<T> {
+impl<T: DeviceElement + Debug> Debug for MemBuffer<T> {
     #[inline(always)]
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let vec = self.to_vec(EMPTY).map_err(|_| core::fmt::Error)?;
@@ -366,7 +780,7 @@ impl<T: Copy + Unpin + Debug> Debug for MemBuffer<T> {
     }
 }
 
-impl<T: Copy + Unpin> Drop for MemBuffer<T> {
+impl<T: DeviceElement> Drop for MemBuffer<T> {
     #[inline(always)]
     fn drop(&mut self) {
         unsafe {
@@ -375,5 +789,5 @@ impl<T: Copy + Unpin> Drop for MemBuffer<T> {
     }
 }
 
-unsafe impl<T: Send + Copy + Unpin> Send for MemBuffer<T> {}
-unsafe impl<T: Sync + Copy + Unpin> Sync for MemBuffer<T> {}
\ No newline at end of file
+unsafe impl<T: Send + DeviceElement> Send for MemBuffer<T> {}
+unsafe impl<T: Sync + DeviceElement> Sync for MemBuffer<T> {}
\ No newline at end of file
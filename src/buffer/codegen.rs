@@ -0,0 +1,61 @@
+use alloc::{collections::BTreeMap, string::String};
+use parking_lot::Mutex;
+use crate::prelude::{Context, CommandQueue, Program, Result, Error};
+use super::ClType;
+
+#[cfg(feature = "error-stack")]
+use alloc::format;
+
+/// Cache of programs generated for a given element type, by context and key.
+/// Programs hold a reference to their context, so cached contexts are never released.
+pub(crate) struct ProgramCache {
+    programs: Mutex<BTreeMap<(usize, &'static str, String), Program>>
+}
+
+impl ProgramCache {
+    #[inline(always)]
+    pub fn new () -> Self {
+        Self { programs: Mutex::new(BTreeMap::new()) }
+    }
+
+    /// Returns the program for ```T``` and ```key```, compiling ```source``` if it isn't cached.
+    /// The source is prefixed with the definition of ```T``` as the OpenCL C counterpart of the element type.
+    pub fn get_or_build<T: ClType> (&self, ctx: &Context, key: &str, source: impl FnOnce() -> String) -> Result<Program> {
+        let map_key = (ctx.0 as usize, T::NAME, String::from(key));
+        if let Some(program) = self.programs.lock().get(&map_key) {
+            return Ok(program.clone())
+        }
+
+        let mut full = String::new();
+        if T::NAME == "double" {
+            if !ctx.devices()?.iter().all(|x| x.has_f64().unwrap_or(false)) {
+                #[cfg(feature = "error-stack")]
+                return Err(error_stack::Report::new(Error::InvalidCompilerOptions).attach_printable("Double precision is not supported on this context"));
+                #[cfg(not(feature = "error-stack"))]
+                return Err(Error::InvalidCompilerOptions);
+            }
+
+            full.push_str("#pragma OPENCL EXTENSION cl_khr_fp64 : enable\n");
+        }
+
+        full.push_str("#define T ");
+        full.push_str(T::NAME);
+        full.push('\n');
+        full.push_str(&source());
+
+        // compiling can take a while, so the lock isn't held meanwhile
+        let program = Program::from_source_with_context(ctx, &full)?;
+        Ok(self.programs.lock().entry(map_key).or_insert(program).clone())
+    }
+}
+
+/// Returns the largest power of two local size supported by the device of ```queue```, such that ```scratch``` arrays of ```wgs``` elements fit in local memory
+pub(crate) fn pow2_local_size<T: ClType> (queue: &CommandQueue, scratch: usize) -> Result<usize> {
+    let device = queue.device()?;
+    let local_mem = usize::try_from(device.local_mem_size()?.get()).unwrap_or(usize::MAX);
+    let wgs = device.max_work_group_size()?.get()
+        .min(local_mem / (scratch.max(1) * core::mem::size_of::<T>()))
+        .max(1);
+
+    Ok(1 << wgs.ilog2())
+}
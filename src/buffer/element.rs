@@ -0,0 +1,48 @@
+/// Types whose values can be safely copied between the host and OpenCL devices as raw bytes.
+/// 
+/// This trait can be derived (with the ```derive``` feature) for ```#[repr(C)]``` and ```#[repr(transparent)]``` structs whose fields all implement [```DeviceElement```] and that contain no padding.
+/// # Safety
+/// Implementors must be ```'static```, not be zero-sized, contain no padding bytes, no pointers or references, and every bit pattern of ```size_of::<Self>()``` bytes must be a valid value of ```Self```
+pub unsafe trait DeviceElement: 'static + Copy + Unpin {}
+
+macro_rules! impl_element {
+    ($($ty:ty),+) => {
+        $(
+            unsafe impl DeviceElement for $ty {}
+        )+
+    };
+}
+
+impl_element! {
+    u8, u16, u32, u64, u128, usize,
+    i8, i16, i32, i64, i128, isize,
+    f32, f64
+}
+
+// zero-sized elements aren't allowed, since buffer lengths are computed by dividing by the element size
+unsafe impl<T: DeviceElement, const N: usize> DeviceElement for [T; N] where [(); N - 1]: {}
+
+
+/// Scalar numeric element types with an OpenCL C counterpart, used to generate kernels for buffers of ```Self```
+/// # Safety
+/// [```NAME```](ClType::NAME) must be the name of the OpenCL C type with the same size and representation as ```Self```
+pub unsafe trait ClType: DeviceElement + num_traits::Num + num_traits::NumCast + num_traits::Bounded + PartialOrd {
+    /// Name of the type in OpenCL C
+    const NAME: &'static str;
+}
+
+macro_rules! impl_cl_type {
+    ($($ty:ty => $name:literal),+) => {
+        $(
+            unsafe impl ClType for $ty {
+                const NAME: &'static str = $name;
+            }
+        )+
+    };
+}
+
+impl_cl_type! {
+    u8 => "uchar", u16 => "ushort", u32 => "uint", u64 => "ulong",
+    i8 => "char", i16 => "short", i32 => "int", i64 => "long",
+    f32 => "float", f64 => "double"
+}
@@ -0,0 +1,377 @@
+use core::{fmt::Write, ops::{Add, Sub, Mul, Div, Neg}};
+use alloc::{boxed::Box, vec::Vec, vec, string::String};
+use num_traits::Float;
+use crate::{prelude::*, kernel::Kernel, event::various::Swap, buffer::{MemFlag, ClType}};
+use super::{codegen::ProgramCache, ops::enqueue_elementwise};
+
+#[cfg(feature = "error-stack")]
+use alloc::format;
+
+lazy_static! {
+    static ref PROGRAMS : ProgramCache = ProgramCache::new();
+}
+
+/// Starts a lazy element-wise expression over ```buffer```
+#[inline(always)]
+pub fn expr<T: ClType> (buffer: &MemBuffer<T>) -> Expr<'_, T> {
+    Expr(Node::Buffer(buffer))
+}
+
+/// Lazy element-wise expression over buffers of ```T```.
+///
+/// Expressions are built with the arithmetic operators and the methods of [```Expr```], and nothing is enqueued until they're evaluated,
+/// at which point the whole expression is compiled into a single kernel. Programs are cached by the shape of the expression, so scalars
+/// can change between evaluations without triggering a recompilation.
+/// ```ignore
+/// let out = (expr(&a) * 2. + expr(&b).sqrt()).eval(EMPTY)?.wait()?;
+/// ```
+#[derive(Clone)]
+pub struct Expr<'a, T: ClType> (Node<'a, T>);
+
+#[derive(Clone)]
+enum Node<'a, T: ClType> {
+    Buffer(&'a MemBuffer<T>),
+    Scalar(T),
+    Neg(Box<Node<'a, T>>),
+    Infix(&'static str, Box<Node<'a, T>>, Box<Node<'a, T>>),
+    Call(&'static str, Vec<Node<'a, T>>)
+}
+
+impl<'a, T: ClType> Expr<'a, T> {
+    /// Returns the element-wise minimum of both expressions
+    #[inline(always)]
+    pub fn min (self, rhs: impl Into<Expr<'a, T>>) -> Self {
+        Self(Node::Call("min", vec![self.0, rhs.into().0]))
+    }
+
+    /// Returns the element-wise maximum of both expressions
+    #[inline(always)]
+    pub fn max (self, rhs: impl Into<Expr<'a, T>>) -> Self {
+        Self(Node::Call("max", vec![self.0, rhs.into().0]))
+    }
+
+    /// Returns the OpenCL C source of the kernel that evaluates the expression
+    #[inline(always)]
+    pub fn source (&self) -> String {
+        Codegen::new(self).source()
+    }
+
+    /// Evaluates the expression into a new buffer.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn eval (&self, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Swap<MemBuffer<T>, BaseEvent>> {
+        self.eval_with_queue(CommandQueue::default(), wait)
+    }
+
+    /// Evaluates the expression into a new buffer.
+    /// # Errors
+    /// Returns [```Error::InvalidBufferSize```] if the buffers of the expression have different lengths, or if it has no buffers
+    pub fn eval_with_queue (&self, queue: &CommandQueue, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Swap<MemBuffer<T>, BaseEvent>> {
+        let codegen = Codegen::new(self);
+        let len = codegen.len(None)?;
+
+        let out = unsafe { MemBuffer::<T>::uninit_with_context(&queue.context()?, len, MemFlag::READ_WRITE)? };
+        let evt = codegen.enqueue(queue, &out, len, wait)?;
+        Ok(evt.swap(out))
+    }
+
+    /// Evaluates the expression into ```out```.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn eval_into (&self, out: &mut MemBuffer<T>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        self.eval_into_with_queue(CommandQueue::default(), out, wait)
+    }
+
+    /// Evaluates the expression into ```out```.
+    /// # Errors
+    /// Returns [```Error::InvalidBufferSize```] if ```out``` and the buffers of the expression have different lengths
+    pub fn eval_into_with_queue (&self, queue: &CommandQueue, out: &mut MemBuffer<T>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        let codegen = Codegen::new(self);
+        let len = codegen.len(Some(&*out))?;
+        codegen.enqueue(queue, out, len, wait)
+    }
+
+    #[inline(always)]
+    fn call (self, f: &'static str) -> Self {
+        Self(Node::Call(f, vec![self.0]))
+    }
+}
+
+impl<'a, T: ClType + Float> Expr<'a, T> {
+    /// Returns the element-wise square root of the expression
+    #[inline(always)]
+    pub fn sqrt (self) -> Self {
+        self.call("sqrt")
+    }
+
+    /// Returns the element-wise exponential of the expression
+    #[inline(always)]
+    pub fn exp (self) -> Self {
+        self.call("exp")
+    }
+
+    /// Returns the element-wise natural logarithm of the expression
+    #[inline(always)]
+    pub fn ln (self) -> Self {
+        self.call("log")
+    }
+
+    /// Returns the element-wise sine of the expression
+    #[inline(always)]
+    pub fn sin (self) -> Self {
+        self.call("sin")
+    }
+
+    /// Returns the element-wise cosine of the expression
+    #[inline(always)]
+    pub fn cos (self) -> Self {
+        self.call("cos")
+    }
+
+    /// Returns the element-wise tangent of the expression
+    #[inline(always)]
+    pub fn tan (self) -> Self {
+        self.call("tan")
+    }
+
+    /// Returns the element-wise hyperbolic tangent of the expression
+    #[inline(always)]
+    pub fn tanh (self) -> Self {
+        self.call("tanh")
+    }
+
+    /// Returns the element-wise absolute value of the expression
+    #[inline(always)]
+    pub fn abs (self) -> Self {
+        self.call("fabs")
+    }
+
+    /// Raises every element of the expression to the power of ```rhs```
+    #[inline(always)]
+    pub fn powf (self, rhs: impl Into<Expr<'a, T>>) -> Self {
+        Self(Node::Call("pow", vec![self.0, rhs.into().0]))
+    }
+}
+
+impl<'a, T: ClType> From<&'a MemBuffer<T>> for Expr<'a, T> {
+    #[inline(always)]
+    fn from (buffer: &'a MemBuffer<T>) -> Self {
+        expr(buffer)
+    }
+}
+
+impl<T: ClType> From<T> for Expr<'_, T> {
+    #[inline(always)]
+    fn from (scalar: T) -> Self {
+        Self(Node::Scalar(scalar))
+    }
+}
+
+macro_rules! impl_ops {
+    ($($trait:ident::$fn:ident => $op:literal),+) => {
+        $(
+            impl<'a, T: ClType> $trait for Expr<'a, T> {
+                type Output = Self;
+
+                #[inline(always)]
+                fn $fn (self, rhs: Self) -> Self {
+                    Self(Node::Infix($op, Box::new(self.0), Box::new(rhs.0)))
+                }
+            }
+
+            impl<'a, T: ClType> $trait<&'a MemBuffer<T>> for Expr<'a, T> {
+                type Output = Self;
+
+                #[inline(always)]
+                fn $fn (self, rhs: &'a MemBuffer<T>) -> Self {
+                    self.$fn(expr(rhs))
+                }
+            }
+
+            impl<'a, T: ClType> $trait<T> for Expr<'a, T> {
+                type Output = Self;
+
+                #[inline(always)]
+                fn $fn (self, rhs: T) -> Self {
+                    self.$fn(Expr::from(rhs))
+                }
+            }
+        )+
+    };
+}
+
+impl_ops! {
+    Add::add => "+",
+    Sub::sub => "-",
+    Mul::mul => "*",
+    Div::div => "/"
+}
+
+macro_rules! impl_scalar_lhs {
+    ($($ty:ty),+) => {
+        $(
+            impl<'a> Add<Expr<'a, $ty>> for $ty {
+                type Output = Expr<'a, $ty>;
+
+                #[inline(always)]
+                fn add (self, rhs: Expr<'a, $ty>) -> Self::Output {
+                    Expr::from(self) + rhs
+                }
+            }
+
+            impl<'a> Sub<Expr<'a, $ty>> for $ty {
+                type Output = Expr<'a, $ty>;
+
+                #[inline(always)]
+                fn sub (self, rhs: Expr<'a, $ty>) -> Self::Output {
+                    Expr::from(self) - rhs
+                }
+            }
+
+            impl<'a> Mul<Expr<'a, $ty>> for $ty {
+                type Output = Expr<'a, $ty>;
+
+                #[inline(always)]
+                fn mul (self, rhs: Expr<'a, $ty>) -> Self::Output {
+                    Expr::from(self) * rhs
+                }
+            }
+
+            impl<'a> Div<Expr<'a, $ty>> for $ty {
+                type Output = Expr<'a, $ty>;
+
+                #[inline(always)]
+                fn div (self, rhs: Expr<'a, $ty>) -> Self::Output {
+                    Expr::from(self) / rhs
+                }
+            }
+        )+
+    };
+}
+
+impl_scalar_lhs! {
+    u8, u16, u32, u64,
+    i8, i16, i32, i64,
+    f32, f64
+}
+
+impl<T: ClType + Neg<Output = T>> Neg for Expr<'_, T> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg (self) -> Self {
+        Self(Node::Neg(Box::new(self.0)))
+    }
+}
+
+/// Flattened expression, where every distinct buffer and every scalar is a kernel argument
+struct Codegen<'a, T: ClType> {
+    buffers: Vec<&'a MemBuffer<T>>,
+    scalars: Vec<T>,
+    body: String
+}
+
+impl<'a, T: ClType> Codegen<'a, T> {
+    fn new (expr: &Expr<'a, T>) -> Self {
+        let mut this = Self { buffers: Vec::new(), scalars: Vec::new(), body: String::new() };
+        this.visit(&expr.0);
+        this
+    }
+
+    fn visit (&mut self, node: &Node<'a, T>) {
+        match node {
+            Node::Buffer(buffer) => {
+                // buffers used more than once are only loaded once
+                let idx = match self.buffers.iter().position(|x| x.0 == buffer.0) {
+                    Some(idx) => idx,
+                    None => {
+                        self.buffers.push(buffer);
+                        self.buffers.len() - 1
+                    }
+                };
+
+                write!(self.body, "b{idx}[id]").unwrap();
+            },
+
+            Node::Scalar(x) => {
+                write!(self.body, "s{}", self.scalars.len()).unwrap();
+                self.scalars.push(*x);
+            },
+
+            // intermediate results are cast back to `T`, so narrow integers wrap around like they do in Rust
+            Node::Neg(x) => {
+                self.body.push_str("((T)-");
+                self.visit(x);
+                self.body.push(')');
+            },
+
+            Node::Infix(op, lhs, rhs) => {
+                self.body.push_str("((T)(");
+                self.visit(lhs);
+                write!(self.body, " {op} ").unwrap();
+                self.visit(rhs);
+                self.body.push_str("))");
+            },
+
+            Node::Call(f, args) => {
+                write!(self.body, "{f}(").unwrap();
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        self.body.push_str(", ");
+                    }
+                    self.visit(arg);
+                }
+                self.body.push(')');
+            }
+        }
+    }
+
+    fn source (&self) -> String {
+        let mut source = String::from("void kernel eval (");
+        for i in 0..self.buffers.len() {
+            write!(source, "__global const T *b{i}, ").unwrap();
+        }
+        for i in 0..self.scalars.len() {
+            write!(source, "const T s{i}, ").unwrap();
+        }
+
+        write!(source, "__global T *out) {{\n    size_t id = get_global_id(0);\n    out[id] = {};\n}}", self.body).unwrap();
+        source
+    }
+
+    /// Returns the length shared by all the buffers of the expression (and ```out```)
+    fn len (&self, out: Option<&MemBuffer<T>>) -> Result<usize> {
+        let mut lens = Vec::with_capacity(self.buffers.len() + 1);
+        for buffer in self.buffers.iter().copied().chain(out) {
+            lens.push(buffer.len()?);
+        }
+
+        // expressions made only of scalars have no length
+        match lens.first() {
+            Some(&len) if lens.iter().all(|x| *x == len) => Ok(len),
+            #[cfg(feature = "error-stack")]
+            _ => Err(error_stack::Report::new(Error::InvalidBufferSize).attach_printable(format!("expected buffers of the same length, found {lens:?}"))),
+            #[cfg(not(feature = "error-stack"))]
+            _ => Err(Error::InvalidBufferSize)
+        }
+    }
+
+    fn enqueue (&self, queue: &CommandQueue, out: &MemBuffer<T>, len: usize, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        // the body only depends on the shape of the expression, not on its buffers or scalars
+        let program = PROGRAMS.get_or_build::<T>(&queue.context()?, &self.body, || self.source())?;
+        let mut kernel = unsafe { Kernel::new_unchecked(&program, "eval")? };
+
+        let mut idx = 0;
+        for buffer in self.buffers.iter() {
+            kernel.set_mem_arg(idx, buffer)?;
+            idx += 1;
+        }
+        for scalar in self.scalars.iter() {
+            kernel.set_arg(idx, *scalar)?;
+            idx += 1;
+        }
+
+        kernel.set_mem_arg(idx, out)?;
+        enqueue_elementwise(queue, &mut kernel, len, wait)
+    }
+}
@@ -1,4 +1,4 @@
-use opencl_sys::{cl_mem_flags, CL_MEM_READ_WRITE, CL_MEM_WRITE_ONLY, CL_MEM_READ_ONLY, CL_MEM_USE_HOST_PTR, CL_MEM_ALLOC_HOST_PTR, CL_MEM_COPY_HOST_PTR};
+use opencl_sys::{cl_mem_flags, cl_mem_migration_flags, CL_MIGRATE_MEM_OBJECT_HOST, CL_MIGRATE_MEM_OBJECT_CONTENT_UNDEFINED, CL_MEM_READ_WRITE, CL_MEM_WRITE_ONLY, CL_MEM_READ_ONLY, CL_MEM_USE_HOST_PTR, CL_MEM_ALLOC_HOST_PTR, CL_MEM_COPY_HOST_PTR};
 
 bitflags::bitflags! {
     /// A bit-field that is used to specify allocation and usage information such as the memory arena that should be used to allocate the buffer object and how it will be used.
@@ -21,4 +21,16 @@ impl Default for MemFlag {
     fn default() -> Self {
         Self::READ_WRITE
     }
+}
+
+bitflags::bitflags! {
+    /// A bit-field that is used to specify migration options when migrating memory objects between devices.
+    #[derive(Default)]
+    #[repr(transparent)]
+    pub struct MemMigrationFlag : cl_mem_migration_flags {
+        /// This flag indicates that the specified set of memory objects are to be migrated to the host, regardless of the target command-queue.
+        const HOST = CL_MIGRATE_MEM_OBJECT_HOST;
+        /// This flag indicates that the contents of the set of memory objects are undefined after migration. The specified set of memory objects are migrated to the device associated with the command-queue without incurring the overhead of migrating their contents.
+        const CONTENT_UNDEFINED = CL_MIGRATE_MEM_OBJECT_CONTENT_UNDEFINED;
+    }
 }
\ No newline at end of file
@@ -0,0 +1,25 @@
+use core::{ptr::NonNull, ffi::c_void};
+use alloc::boxed::Box;
+use opencl_sys::cl_mem;
+use super::{MemFlag, MemObjectType};
+
+/// Snapshot of the state of a [```MemBuffer```](super::MemBuffer), as returned by [```MemBuffer::info```](super::MemBuffer::info). Values are only valid at the moment the snapshot is taken.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MemBufferInfo {
+    pub id: cl_mem,
+    pub ty: MemObjectType,
+    pub flags: MemFlag,
+    /// Size of the buffer, in bytes
+    pub size: usize,
+    /// Number of elements in the buffer
+    pub len: usize,
+    pub host_ptr: Option<NonNull<c_void>>,
+    pub map_count: u32,
+    pub reference_count: u32,
+    /// Offset, in bytes, inside the parent buffer. Always ```0``` if the buffer isn't a sub-buffer.
+    pub offset: usize,
+    #[cfg(feature = "cl2")]
+    pub uses_svm_pointer: bool,
+    /// Information about the buffer this buffer is a sub-buffer of, if any
+    pub parent: Option<Box<MemBufferInfo>>
+}
\ No newline at end of file
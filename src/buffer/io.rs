@@ -1,21 +1,26 @@
 #[cfg(test)]
 extern crate std;
 
-use std::io::{Read, Write};
-use alloc::vec::Vec;
-use crate::{prelude::{Result, Context, Error, CommandQueue, Event, BaseEvent}};
-use super::{MemBuffer, MemFlag};
-#[cfg(feature = "async")]
-use futures::{AsyncReadExt, AsyncWriteExt};
-
-impl<T: Copy + Unpin> MemBuffer<T> {
+use std::io::{Read, Write, Seek, SeekFrom, ErrorKind};
+use core::ptr::NonNull;
+use alloc::{vec::Vec, boxed::Box};
+use crate::{prelude::{Result, Context, Error, CommandQueue, Event, BaseEvent}, error::ErrorCL};
+use super::{MemBuffer, MemFlag, DeviceElement, ReadSlice, WriteSlice};
+
+#[cfg(feature = "error-stack")]
+use alloc::format;
+
+/// Default size (in bytes) of the staging memory used by [```BufferReader```] and [```BufferWriter```]
+pub const DEFAULT_CHUNK_SIZE: usize = 1 << 20;
+
+impl<T: DeviceElement> MemBuffer<T> {
     #[cfg(feature = "def")]
     #[inline(always)]
-    pub unsafe fn from_io<R: ?Sized + Read> (flags: MemFlag, src: &mut R) -> Result<Self> {
+    pub fn from_io<R: ?Sized + Read> (flags: MemFlag, src: &mut R) -> Result<Self> {
         Self::from_io_with_context(Context::default(), flags, src)
     }
 
-    pub unsafe fn from_io_with_context<R: ?Sized + Read> (ctx: &Context, flags: MemFlag, src: &mut R) -> Result<Self> {
+    pub fn from_io_with_context<R: ?Sized + Read> (ctx: &Context, flags: MemFlag, src: &mut R) -> Result<Self> {
         let mut buff = Vec::<u8>::with_capacity(core::mem::size_of::<T>());
         let read = src.read_to_end(&mut buff);
         #[cfg(feature = "error-stack")]
@@ -30,8 +35,9 @@ impl<T: Copy + Unpin> MemBuffer<T> {
             return Err(Error::InvalidBufferSize);
         }
 
-        let buff = core::slice::from_raw_parts(buff.as_ptr().cast(), buff.len() / core::mem::size_of::<T>());
-        Self::with_context(ctx, flags, &buff)
+        // OpenCL copies the host memory byte by byte, so it doesn't need to be aligned to `T`
+        let len = buff.len() / core::mem::size_of::<T>();
+        unsafe { Self::with_host_ptr(ctx, len, flags | MemFlag::COPY_HOST_PTR, NonNull::new(buff.as_mut_ptr().cast())) }
     }
 
     #[cfg(feature = "def")]
@@ -41,9 +47,9 @@ impl<T: Copy + Unpin> MemBuffer<T> {
     }
 
     pub fn write_into_with_queue<W: ?Sized + Write> (&self, queue: &CommandQueue, dst: &mut W, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<()> {
-        let vec = self.to_vec_with_queue(queue, wait)?.wait()?;
-        let bytes = unsafe { core::slice::from_raw_parts::<u8>(vec.as_ptr().cast(), vec.len().checked_mul(core::mem::size_of::<T>()).unwrap()) };
-        let write = dst.write_all(bytes);
+        queue.marker(wait)?.wait()?;
+        let mut reader = self.reader_with_queue(queue, DEFAULT_CHUNK_SIZE);
+        let write = std::io::copy(&mut reader, dst);
 
         #[cfg(feature = "error-stack")]
         write.map_err(|e| error_stack::Report::new(Error::MemObjectAllocationFailure).attach_printable(e))?;
@@ -54,50 +60,246 @@ impl<T: Copy + Unpin> MemBuffer<T> {
     }
 }
 
-#[cfg(feature = "async")]
-impl<T: 'static + Copy + Unpin> MemBuffer<T> {
+impl<T: DeviceElement> MemBuffer<T> {
+    /// Creates a buffer of ```len``` elements and fills it with the bytes of ```src```, one chunk at a time.
     #[cfg(feature = "def")]
     #[inline(always)]
-    pub async unsafe fn from_io_async<R: ?Sized + Unpin + AsyncReadExt> (flags: MemFlag, src: &mut R) -> Result<Self> {
-        Self::from_io_with_context_async(Context::default(), flags, src).await
+    pub fn from_reader_sized<R: ?Sized + Read> (len: usize, flags: MemFlag, src: &mut R) -> Result<Self> {
+        Self::from_reader_sized_with_queue(CommandQueue::default(), len, flags, src)
     }
 
-    pub async unsafe fn from_io_with_context_async<R: ?Sized + Unpin + AsyncReadExt> (ctx: &Context, flags: MemFlag, src: &mut R) -> Result<Self> {
-        let mut buff = Vec::<u8>::with_capacity(core::mem::size_of::<T>());
-        let read = src.read_to_end(&mut buff).await;
+    /// Creates a buffer of ```len``` elements and fills it with the bytes of ```src```, one chunk at a time. The buffer is created on the context of ```queue```.
+    /// # Errors
+    /// Returns [```Error::InvalidBufferSize```] if ```src``` ends before the buffer is filled, and [```Error::MemObjectAllocationFailure```] if reading from ```src``` fails
+    pub fn from_reader_sized_with_queue<R: ?Sized + Read> (queue: &CommandQueue, len: usize, flags: MemFlag, src: &mut R) -> Result<Self> {
+        let ctx = queue.context()?;
+        let flags = flags - (MemFlag::COPY_HOST_PTR | MemFlag::USE_HOST_PTR);
+        let mut this = unsafe { Self::uninit_with_context(&ctx, len, flags)? };
+        let size = this.byte_size()? as u64;
+
+        let mut writer = this.writer_with_queue(queue, DEFAULT_CHUNK_SIZE);
+        let copy = std::io::copy(&mut src.take(size), &mut writer).and_then(|n| writer.flush().map(|_| n));
+        drop(writer);
 
         #[cfg(feature = "error-stack")]
-        read.map_err(|e| error_stack::Report::new(Error::MemObjectAllocationFailure).attach_printable(e))?;
+        let copy = copy.map_err(|e| error_stack::Report::new(Error::MemObjectAllocationFailure).attach_printable(e))?;
         #[cfg(not(feature = "error-stack"))]
-        read.map_err(|_| Error::MemObjectAllocationFailure)?; 
+        let copy = copy.map_err(|_| Error::MemObjectAllocationFailure)?;
 
-        if buff.len() % core::mem::size_of::<T>() != 0 {
+        if copy != size {
             #[cfg(feature = "error-stack")]
-            return Err(error_stack::Report::new(Error::InvalidBufferSize).attach_printable("Buffer size is not a multiple of the element size"));
+            return Err(error_stack::Report::new(Error::InvalidBufferSize).attach_printable(format!("reader ended after {copy} of {size} bytes")));
             #[cfg(not(feature = "error-stack"))]
             return Err(Error::InvalidBufferSize);
         }
 
-        let buff = core::slice::from_raw_parts(buff.as_ptr().cast(), buff.len() / core::mem::size_of::<T>());
-        Self::with_context(ctx, flags, &buff)
+        Ok(this)
     }
 
+    /// Returns a reader over the bytes of the buffer, which transfers ```chunk_size``` bytes at a time from the default queue.
+    /// # Panics
+    /// Panics if ```chunk_size``` is zero
     #[cfg(feature = "def")]
     #[inline(always)]
-    pub fn write_into_async<W: ?Sized + Write> (&self, dst: &mut W, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<()> {
-        self.write_into_with_queue(CommandQueue::default(), dst, wait)
+    pub fn reader (&self, chunk_size: usize) -> BufferReader<'_> {
+        self.reader_with_queue(CommandQueue::default(), chunk_size)
     }
 
-    pub async fn write_into_with_queue_async<W: ?Sized + Unpin + AsyncWriteExt> (&self, queue: &CommandQueue, dst: &mut W, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<()> {
-        let vec = self.to_vec_with_queue(queue, wait)?.await?;
-        let bytes = unsafe { core::slice::from_raw_parts::<u8>(vec.as_ptr().cast(), vec.len().checked_mul(core::mem::size_of::<T>()).unwrap()) };
-        let write = dst.write_all(bytes).await;
+    /// Returns a reader over the bytes of the buffer, which transfers ```chunk_size``` bytes at a time from ```queue```.
+    /// # Panics
+    /// Panics if ```chunk_size``` is zero
+    #[inline(always)]
+    pub fn reader_with_queue<'a> (&'a self, queue: &'a CommandQueue, chunk_size: usize) -> BufferReader<'a> {
+        BufferReader::new(queue, self.as_bytes(), chunk_size)
+    }
 
-        #[cfg(feature = "error-stack")]
-        write.map_err(|e| error_stack::Report::new(Error::MemObjectAllocationFailure).attach_printable(e))?;
-        #[cfg(not(feature = "error-stack"))]
-        write.map_err(|_| Error::MemObjectAllocationFailure)?;
+    /// Returns a writer over the bytes of the buffer, which transfers ```chunk_size``` bytes at a time from the default queue.
+    /// # Panics
+    /// Panics if ```chunk_size``` is zero
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn writer (&mut self, chunk_size: usize) -> BufferWriter<'_> {
+        self.writer_with_queue(CommandQueue::default(), chunk_size)
+    }
+
+    /// Returns a writer over the bytes of the buffer, which transfers ```chunk_size``` bytes at a time from ```queue```.
+    /// # Panics
+    /// Panics if ```chunk_size``` is zero
+    #[inline(always)]
+    pub fn writer_with_queue<'a> (&'a mut self, queue: &'a CommandQueue, chunk_size: usize) -> BufferWriter<'a> {
+        BufferWriter::new(queue, self.as_bytes_mut(), chunk_size)
+    }
+}
+
+/// Reader over the bytes of a [```MemBuffer```]. Data is read from the device in chunks, through a reused staging buffer.
+pub struct BufferReader<'a> {
+    queue: &'a CommandQueue,
+    bytes: ReadSlice<'a, u8>,
+    staging: Box<[u8]>,
+    size: u64,
+    /// Position of the first byte of ```staging``` inside the buffer
+    offset: u64,
+    start: usize,
+    end: usize
+}
+
+impl<'a> BufferReader<'a> {
+    fn new (queue: &'a CommandQueue, bytes: ReadSlice<'a, u8>, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "chunk size must be greater than zero");
+        let size = bytes.byte_size().map_or(0, |x| x as u64);
+
+        Self {
+            queue,
+            bytes,
+            staging: alloc::vec![0; chunk_size].into_boxed_slice(),
+            size,
+            offset: 0,
+            start: 0,
+            end: 0
+        }
+    }
+
+    #[inline(always)]
+    fn position (&self) -> u64 {
+        self.offset + self.start as u64
+    }
+
+    fn fill (&mut self) -> Result<()> {
+        let pos = self.position();
+        let len = usize::try_from(self.size.saturating_sub(pos)).unwrap_or(usize::MAX).min(self.staging.len());
+
+        if len > 0 {
+            self.bytes.read_into_with_queue(self.queue, pos as usize, &mut self.staging[..len], crate::prelude::EMPTY)?.wait()?;
+        }
+
+        self.offset = pos;
+        self.start = 0;
+        self.end = len;
+        Ok(())
+    }
+}
+
+impl Read for BufferReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.start == self.end {
+            self.fill().map_err(io_error)?;
+        }
+
+        let len = buf.len().min(self.end - self.start);
+        buf[..len].copy_from_slice(&self.staging[self.start..self.start + len]);
+        self.start += len;
+        Ok(len)
+    }
+}
+
+impl Seek for BufferReader<'_> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let pos = seek_position(pos, self.position(), self.size)?;
+
+        // keep the staged chunk if the new position is inside of it
+        match pos.checked_sub(self.offset) {
+            Some(delta) if delta <= self.end as u64 => self.start = delta as usize,
+            _ => {
+                self.offset = pos;
+                self.start = 0;
+                self.end = 0;
+            }
+        }
+
+        Ok(pos)
+    }
+}
+
+/// Writer over the bytes of a [```MemBuffer```]. Data is written to the device in chunks, through a reused staging buffer.
+/// 
+/// Writes past the end of the buffer return ```Ok(0)```. Any pending data is written when the writer is dropped, but errors are ignored, so [```flush```](Write::flush) should be called first.
+pub struct BufferWriter<'a> {
+    queue: &'a CommandQueue,
+    bytes: WriteSlice<'a, u8>,
+    staging: Vec<u8>,
+    chunk_size: usize,
+    size: u64,
+    /// Position of the first byte of ```staging``` inside the buffer
+    offset: u64
+}
+
+impl<'a> BufferWriter<'a> {
+    fn new (queue: &'a CommandQueue, bytes: WriteSlice<'a, u8>, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "chunk size must be greater than zero");
+        let size = bytes.byte_size().map_or(0, |x| x as u64);
+
+        Self {
+            queue,
+            bytes,
+            staging: Vec::with_capacity(chunk_size),
+            chunk_size,
+            size,
+            offset: 0
+        }
+    }
+
+    #[inline(always)]
+    fn position (&self) -> u64 {
+        self.offset + self.staging.len() as u64
+    }
+
+    fn flush_staging (&mut self) -> Result<()> {
+        if !self.staging.is_empty() {
+            self.bytes.write_with_queue(self.queue, self.offset as usize, &self.staging, crate::prelude::EMPTY)?.wait()?;
+            self.offset += self.staging.len() as u64;
+            self.staging.clear();
+        }
 
         Ok(())
     }
+}
+
+impl Write for BufferWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let remaining = usize::try_from(self.size.saturating_sub(self.position())).unwrap_or(usize::MAX);
+        let len = buf.len().min(remaining).min(self.chunk_size - self.staging.len());
+        self.staging.extend_from_slice(&buf[..len]);
+
+        if self.staging.len() == self.chunk_size {
+            self.flush_staging().map_err(io_error)?;
+        }
+
+        Ok(len)
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.flush_staging().map_err(io_error)
+    }
+}
+
+impl Seek for BufferWriter<'_> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let pos = seek_position(pos, self.position(), self.size)?;
+        self.flush_staging().map_err(io_error)?;
+        self.offset = pos;
+        Ok(pos)
+    }
+}
+
+impl Drop for BufferWriter<'_> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        let _ = self.flush_staging();
+    }
+}
+
+pub(super) fn seek_position (pos: SeekFrom, current: u64, size: u64) -> std::io::Result<u64> {
+    let pos = match pos {
+        SeekFrom::Start(x) => Some(x),
+        SeekFrom::End(x) => size.checked_add_signed(x),
+        SeekFrom::Current(x) => current.checked_add_signed(x)
+    };
+
+    pos.ok_or_else(|| std::io::Error::new(ErrorKind::InvalidInput, "invalid seek to a negative or overflowing position"))
+}
+
+#[inline(always)]
+pub(super) fn io_error (e: ErrorCL) -> std::io::Error {
+    std::io::Error::other(alloc::format!("{e}"))
 }
\ No newline at end of file
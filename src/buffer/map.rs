@@ -0,0 +1,174 @@
+use core::{marker::PhantomData, ptr::NonNull, ops::{Deref, DerefMut}, fmt::Debug};
+use alloc::vec::Vec;
+use opencl_sys::{cl_mem, cl_event, clEnqueueUnmapMemObject, clWaitForEvents, clReleaseEvent};
+use crate::prelude::{Result, Error, CommandQueue, BaseEvent};
+use super::DeviceElement;
+
+#[cfg(feature = "error-stack")]
+use alloc::format;
+
+/// Host mapping of a region of a [```MemBuffer```](super::MemBuffer). The region is unmapped when the guard is dropped.
+///
+/// Errors while unmapping on drop are ignored. Use [```unmap```](Self::unmap) to handle them.
+pub struct MapGuard<'a, T: DeviceElement> {
+    inner: RawMap<T>,
+    phtm: PhantomData<&'a [T]>
+}
+
+/// Mutable host mapping of a region of a [```MemBuffer```](super::MemBuffer). The region is unmapped when the guard is dropped.
+///
+/// Errors while unmapping on drop are ignored. Use [```unmap```](Self::unmap) to handle them.
+pub struct MapMutGuard<'a, T: DeviceElement> {
+    inner: RawMap<T>,
+    phtm: PhantomData<&'a mut [T]>
+}
+
+impl<'a, T: DeviceElement> MapGuard<'a, T> {
+    /// # Safety
+    /// ```ptr``` must be the result of mapping ```len``` elements of ```mem``` for reading on ```queue```
+    #[inline(always)]
+    pub(crate) unsafe fn from_raw_parts (queue: CommandQueue, mem: cl_mem, ptr: NonNull<T>, len: usize) -> Self {
+        Self { inner: RawMap { queue, mem, ptr, len }, phtm: PhantomData }
+    }
+
+    /// Unmaps the region without blocking, returning the event of the unmap command.
+    #[inline(always)]
+    pub fn unmap (self, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        let inner = unsafe { core::ptr::read(&core::mem::ManuallyDrop::new(self).inner) };
+        inner.unmap(wait)
+    }
+}
+
+impl<'a, T: DeviceElement> MapMutGuard<'a, T> {
+    /// # Safety
+    /// ```ptr``` must be the result of mapping ```len``` elements of ```mem``` for reading and writing on ```queue```
+    #[inline(always)]
+    pub(crate) unsafe fn from_raw_parts (queue: CommandQueue, mem: cl_mem, ptr: NonNull<T>, len: usize) -> Self {
+        Self { inner: RawMap { queue, mem, ptr, len }, phtm: PhantomData }
+    }
+
+    /// Unmaps the region without blocking, returning the event of the unmap command.
+    #[inline(always)]
+    pub fn unmap (self, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        let inner = unsafe { core::ptr::read(&core::mem::ManuallyDrop::new(self).inner) };
+        inner.unmap(wait)
+    }
+}
+
+impl<T: DeviceElement> Deref for MapGuard<'_, T> {
+    type Target = [T];
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.inner.as_slice()
+    }
+}
+
+impl<T: DeviceElement> Deref for MapMutGuard<'_, T> {
+    type Target = [T];
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.inner.as_slice()
+    }
+}
+
+impl<T: DeviceElement> DerefMut for MapMutGuard<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { core::slice::from_raw_parts_mut(self.inner.ptr.as_ptr(), self.inner.len) }
+    }
+}
+
+impl<T: DeviceElement + Debug> Debug for MapGuard<'_, T> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.deref(), f)
+    }
+}
+
+impl<T: DeviceElement + Debug> Debug for MapMutGuard<'_, T> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.deref(), f)
+    }
+}
+
+unsafe impl<T: DeviceElement + Sync> Send for MapGuard<'_, T> {}
+unsafe impl<T: DeviceElement + Sync> Sync for MapGuard<'_, T> {}
+unsafe impl<T: DeviceElement + Send> Send for MapMutGuard<'_, T> {}
+unsafe impl<T: DeviceElement + Sync> Sync for MapMutGuard<'_, T> {}
+
+struct RawMap<T> {
+    queue: CommandQueue,
+    mem: cl_mem,
+    ptr: NonNull<T>,
+    len: usize
+}
+
+impl<T> RawMap<T> {
+    #[inline(always)]
+    fn as_slice (&self) -> &[T] {
+        unsafe { core::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    fn enqueue_unmap (&self, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<cl_event> {
+        let wait = wait.into_iter().map(|x| x.as_ref().0).collect::<Vec<_>>();
+        let wait_len = u32::try_from(wait.len()).unwrap();
+        let wait = match wait_len {
+            0 => core::ptr::null(),
+            _ => wait.as_ptr()
+        };
+
+        let mut event : cl_event = core::ptr::null_mut();
+        let err = unsafe { clEnqueueUnmapMemObject(self.queue.0, self.mem, self.ptr.as_ptr().cast(), wait_len, wait, &mut event) };
+        
+        if err == 0 {
+            return Ok(event);
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "error-stack")] {
+                let err = Error::from(err);
+                let report = error_stack::Report::new(err);
+
+                let report = match err {
+                    Error::InvalidCommandQueue => report.attach_printable(format!("'{:?}' is not a valid command-queue", self.queue.0)),
+                    Error::InvalidMemObject => report.attach_printable(format!("'{:?}' is not a valid memory object", self.mem)),
+                    Error::InvalidValue => report.attach_printable(format!("'{:?}' is not a valid pointer returned by a map command", self.ptr)),
+                    Error::InvalidEventWaitList => report.attach_printable("event objects in the event wait list are not valid events"),
+                    Error::InvalidContext => report.attach_printable("the context associated with the command queue and memory object are not the same or the context associated with command queue and events in the event wait list are not the same"),
+                    Error::OutOfResources => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the device"),
+                    Error::OutOfHostMemory => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the host"),
+                    _ => report
+                };
+
+                Err(report)
+            } else {
+                Err(Error::from(err))
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn unmap (self, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        let this = core::mem::ManuallyDrop::new(self);
+        let event = this.enqueue_unmap(wait);
+        // SAFETY: the queue isn't used after this point
+        drop(unsafe { core::ptr::read(&this.queue) });
+        BaseEvent::new(event?)
+    }
+}
+
+impl<T> Drop for RawMap<T> {
+    #[inline]
+    fn drop(&mut self) {
+        // panicking here would abort if the guard is dropped while unwinding
+        if let Ok(event) = self.enqueue_unmap(crate::prelude::EMPTY) {
+            unsafe {
+                let _ = clWaitForEvents(1, &event);
+                let _ = clReleaseEvent(event);
+            }
+        }
+    }
+}
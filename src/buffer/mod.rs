@@ -1,7 +1,37 @@
-flat_mod!(flags, base);
+flat_mod!(element, flags, object, info, base, slice, map, rect, io, pool);
+
+#[cfg(feature = "derive")]
+pub use hlocl_derive::DeviceElement;
+
+#[cfg(feature = "async")]
+flat_mod!(async_io);
+
+#[cfg(feature = "npy")]
+flat_mod!(npy, npz);
 
 #[cfg(feature = "serde")]
-flat_mod!(ser_de);
+flat_mod!(ser_de, seed);
+
+#[cfg(feature = "serde")]
+pub mod serde_bytes;
 
 #[cfg(feature = "rand")]
-flat_mod!(random);
\ No newline at end of file
+flat_mod!(random);
+
+#[cfg(feature = "reduce")]
+flat_mod!(reduce);
+
+#[cfg(feature = "scan")]
+flat_mod!(scan);
+
+#[cfg(feature = "sort")]
+flat_mod!(sort);
+
+#[cfg(feature = "ops")]
+flat_mod!(ops);
+
+#[cfg(feature = "expr")]
+flat_mod!(expr);
+
+#[cfg(any(feature = "reduce", feature = "scan", feature = "ops"))]
+mod codegen;
\ No newline at end of file
@@ -0,0 +1,423 @@
+#[cfg(test)]
+extern crate std;
+
+use core::fmt::{Display, Debug};
+use std::io::{Read, Write};
+use alloc::{vec::Vec, string::{String, ToString}, format};
+use crate::{prelude::{CommandQueue, Event, BaseEvent}, error::ErrorCL};
+use super::{MemBuffer, MemFlag, DeviceElement, DEFAULT_CHUNK_SIZE};
+
+const MAGIC: &[u8; 6] = b"\x93NUMPY";
+
+/// Buffer element that can be stored in a NumPy ```.npy``` file
+/// # Safety
+/// ```Self``` must be laid out as ```size_of::<Self>() / SCALAR_SIZE``` contiguous scalars of type ```KIND``` and size ```SCALAR_SIZE```
+pub unsafe trait NpyElement: DeviceElement {
+    /// NumPy type character of the element's scalars (```'u'```, ```'i'``` or ```'f'```)
+    const KIND: char;
+    /// Size, in bytes, of the element's scalars
+    const SCALAR_SIZE: usize;
+
+    /// Appends the inner dimensions of the element (i.e. the lengths of its array levels) to ```shape```
+    #[inline(always)]
+    fn inner_shape (shape: &mut Vec<usize>) {
+        let _ = shape;
+    }
+
+    /// Returns the NumPy type descriptor of the element's scalars, in native byte order (e.g. ```"<f4"```)
+    #[inline(always)]
+    fn descr () -> String {
+        format!("{}{}{}", byte_order(Self::SCALAR_SIZE), Self::KIND, Self::SCALAR_SIZE)
+    }
+}
+
+macro_rules! impl_npy {
+    ($($kind:literal => $($ty:ty),+);+) => {
+        $(
+            $(
+                unsafe impl NpyElement for $ty {
+                    const KIND: char = $kind;
+                    const SCALAR_SIZE: usize = core::mem::size_of::<$ty>();
+                }
+            )+
+        )+
+    };
+}
+
+impl_npy! {
+    'u' => u8, u16, u32, u64;
+    'i' => i8, i16, i32, i64;
+    'f' => f32, f64
+}
+
+unsafe impl<T: NpyElement, const N: usize> NpyElement for [T; N] where [(); N - 1]: {
+    const KIND: char = T::KIND;
+    const SCALAR_SIZE: usize = T::SCALAR_SIZE;
+
+    #[inline(always)]
+    fn inner_shape (shape: &mut Vec<usize>) {
+        shape.push(N);
+        T::inner_shape(shape)
+    }
+}
+
+/// Error produced while loading or saving NumPy files
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum NpyError {
+    /// The underlying reader or writer failed
+    Io (std::io::Error),
+    /// OpenCL error
+    CL (ErrorCL),
+    /// The file doesn't start with the ```.npy``` magic string
+    InvalidMagic,
+    /// The file's format version isn't supported
+    UnsupportedVersion (u8, u8),
+    /// The header of the file couldn't be parsed
+    InvalidHeader (String),
+    /// The dtype of the file doesn't match the buffer's element type
+    DtypeMismatch { expected: String, found: String },
+    /// The shape of the file doesn't match the buffer's element type
+    ShapeMismatch { inner: Vec<usize>, found: Vec<usize> },
+    /// The file is stored in Fortran (column-major) order, and has more than one dimension
+    FortranOrder,
+    /// The ```.npz``` archive couldn't be read or written
+    Zip (zip::result::ZipError),
+    /// The ```.npz``` archive doesn't contain an array with the requested name
+    NotFound (String)
+}
+
+impl Display for NpyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Io(e) => Display::fmt(e, f),
+            Self::CL(e) => Display::fmt(e, f),
+            Self::InvalidMagic => f.write_str("not a npy file"),
+            Self::UnsupportedVersion(major, minor) => write!(f, "unsupported npy version {major}.{minor}"),
+            Self::InvalidHeader(e) => write!(f, "invalid npy header: {e}"),
+            Self::DtypeMismatch { expected, found } => write!(f, "expected dtype '{expected}', found '{found}'"),
+            Self::ShapeMismatch { inner, found } => write!(f, "shape {found:?} doesn't end with the element's dimensions {inner:?}"),
+            Self::FortranOrder => f.write_str("multi-dimensional arrays in fortran order are not supported"),
+            Self::Zip(e) => Display::fmt(e, f),
+            Self::NotFound(name) => write!(f, "array '{name}' not found in archive")
+        }
+    }
+}
+
+impl std::error::Error for NpyError {}
+
+impl From<std::io::Error> for NpyError {
+    #[inline(always)]
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<ErrorCL> for NpyError {
+    #[inline(always)]
+    fn from(e: ErrorCL) -> Self {
+        Self::CL(e)
+    }
+}
+
+#[cfg(feature = "error-stack")]
+impl From<crate::error::Error> for NpyError {
+    #[inline(always)]
+    fn from(e: crate::error::Error) -> Self {
+        Self::CL(error_stack::Report::new(e))
+    }
+}
+
+impl From<zip::result::ZipError> for NpyError {
+    #[inline(always)]
+    fn from(e: zip::result::ZipError) -> Self {
+        Self::Zip(e)
+    }
+}
+
+/// Parsed header of a ```.npy``` file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NpyHeader {
+    pub descr: String,
+    pub fortran_order: bool,
+    pub shape: Vec<usize>
+}
+
+impl NpyHeader {
+    /// Reads the magic string, version and header of a ```.npy``` file, leaving ```src``` at the start of the array's data
+    pub fn read<R: ?Sized + Read> (src: &mut R) -> Result<Self, NpyError> {
+        let mut magic = [0; 8];
+        src.read_exact(&mut magic)?;
+        if &magic[..6] != MAGIC {
+            return Err(NpyError::InvalidMagic);
+        }
+
+        let len = match (magic[6], magic[7]) {
+            (1, _) => {
+                let mut len = [0; 2];
+                src.read_exact(&mut len)?;
+                u16::from_le_bytes(len) as usize
+            },
+            (2 | 3, _) => {
+                let mut len = [0; 4];
+                src.read_exact(&mut len)?;
+                u32::from_le_bytes(len) as usize
+            },
+            (major, minor) => return Err(NpyError::UnsupportedVersion(major, minor))
+        };
+
+        let mut header = alloc::vec![0; len];
+        src.read_exact(&mut header)?;
+        let header = core::str::from_utf8(&header).map_err(|e| NpyError::InvalidHeader(e.to_string()))?;
+        Self::parse(header)
+    }
+
+    /// Writes the magic string, version and header of a ```.npy``` file
+    pub fn write<W: ?Sized + Write> (&self, dst: &mut W) -> Result<(), NpyError> {
+        let shape = match self.shape.as_slice() {
+            [x] => format!("({x},)"),
+            shape => format!("({})", shape.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))
+        };
+
+        let fortran_order = if self.fortran_order { "True" } else { "False" };
+        let mut header = format!("{{'descr': '{}', 'fortran_order': {fortran_order}, 'shape': {shape}, }}", self.descr);
+
+        // the data must start at a multiple of 64 bytes, and the header must end with a newline
+        let (version, prefix) = match header.len() + 64 > u16::MAX as usize {
+            false => (1u8, 10),
+            true => (2, 12)
+        };
+
+        let padding = (64 - (prefix + header.len() + 1) % 64) % 64;
+        header.extend(core::iter::repeat_n(' ', padding));
+        header.push('\n');
+
+        dst.write_all(MAGIC)?;
+        dst.write_all(&[version, 0])?;
+        match version {
+            1 => dst.write_all(&(header.len() as u16).to_le_bytes())?,
+            _ => dst.write_all(&(header.len() as u32).to_le_bytes())?
+        }
+
+        dst.write_all(header.as_bytes())?;
+        Ok(())
+    }
+
+    fn parse (header: &str) -> Result<Self, NpyError> {
+        let mut parser = Parser(header.trim());
+        let mut descr = None;
+        let mut fortran_order = None;
+        let mut shape = None;
+
+        parser.expect('{')?;
+        loop {
+            if parser.eat('}') {
+                break;
+            }
+
+            let key = parser.string()?;
+            parser.expect(':')?;
+
+            match key {
+                "descr" => descr = Some(parser.string()?.to_string()),
+                "fortran_order" => fortran_order = Some(parser.bool()?),
+                "shape" => shape = Some(parser.tuple()?),
+                other => return Err(NpyError::InvalidHeader(format!("unknown key '{other}'")))
+            }
+
+            if !parser.eat(',') {
+                parser.expect('}')?;
+                break;
+            }
+        }
+
+        match (descr, fortran_order, shape) {
+            (Some(descr), Some(fortran_order), Some(shape)) => Ok(Self { descr, fortran_order, shape }),
+            _ => Err(NpyError::InvalidHeader("missing 'descr', 'fortran_order' or 'shape'".to_string()))
+        }
+    }
+}
+
+/// Minimal parser for the python literals used in ```.npy``` headers
+struct Parser<'a> (&'a str);
+
+impl<'a> Parser<'a> {
+    fn eat (&mut self, c: char) -> bool {
+        self.0 = self.0.trim_start();
+        match self.0.strip_prefix(c) {
+            Some(rest) => {
+                self.0 = rest;
+                true
+            },
+            None => false
+        }
+    }
+
+    fn expect (&mut self, c: char) -> Result<(), NpyError> {
+        match self.eat(c) {
+            true => Ok(()),
+            false => Err(NpyError::InvalidHeader(format!("expected '{c}'")))
+        }
+    }
+
+    fn string (&mut self) -> Result<&'a str, NpyError> {
+        let quote = match self.eat('\'') {
+            true => '\'',
+            false => {
+                self.expect('"')?;
+                '"'
+            }
+        };
+
+        let end = self.0.find(quote).ok_or_else(|| NpyError::InvalidHeader("unterminated string".to_string()))?;
+        let (s, rest) = self.0.split_at(end);
+        self.0 = &rest[1..];
+        Ok(s)
+    }
+
+    fn bool (&mut self) -> Result<bool, NpyError> {
+        self.0 = self.0.trim_start();
+        if let Some(rest) = self.0.strip_prefix("True") {
+            self.0 = rest;
+            return Ok(true);
+        }
+
+        if let Some(rest) = self.0.strip_prefix("False") {
+            self.0 = rest;
+            return Ok(false);
+        }
+
+        Err(NpyError::InvalidHeader("expected a boolean".to_string()))
+    }
+
+    fn tuple (&mut self) -> Result<Vec<usize>, NpyError> {
+        let mut result = Vec::new();
+        self.expect('(')?;
+
+        loop {
+            if self.eat(')') {
+                break;
+            }
+
+            self.0 = self.0.trim_start();
+            let end = self.0.find(|c: char| !c.is_ascii_digit()).unwrap_or(self.0.len());
+            let (num, rest) = self.0.split_at(end);
+            result.push(num.parse().map_err(|_| NpyError::InvalidHeader(format!("invalid dimension '{num}'")))?);
+            self.0 = rest;
+
+            if !self.eat(',') {
+                self.expect(')')?;
+                break;
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl<T: NpyElement> MemBuffer<T> {
+    /// Loads a buffer from a NumPy ```.npy``` file. Files stored in the opposite byte order are converted on the host, chunk by chunk.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn load_npy<R: ?Sized + Read> (flags: MemFlag, src: &mut R) -> Result<Self, NpyError> {
+        Self::load_npy_with_queue(CommandQueue::default(), flags, src)
+    }
+
+    /// Loads a buffer from a NumPy ```.npy``` file. The buffer is created on the context of ```queue```. Files stored in the opposite byte order are converted on the host, chunk by chunk.
+    /// # Errors
+    /// Returns [```NpyError::DtypeMismatch```] if the file's dtype isn't ```T```'s, and [```NpyError::ShapeMismatch```] if the file's shape doesn't end with the dimensions of ```T```
+    pub fn load_npy_with_queue<R: ?Sized + Read> (queue: &CommandQueue, flags: MemFlag, src: &mut R) -> Result<Self, NpyError> {
+        let header = NpyHeader::read(src)?;
+        let swap = check_descr::<T>(&header.descr)?;
+
+        if header.fortran_order && header.shape.len() > 1 {
+            return Err(NpyError::FortranOrder);
+        }
+
+        let mut inner = Vec::new();
+        T::inner_shape(&mut inner);
+        let found = header.shape;
+
+        let len = match found.len().checked_sub(inner.len()) {
+            Some(outer) if found[outer..] == inner[..] => found[..outer].iter().try_fold(1usize, |acc, &x| acc.checked_mul(x)),
+            _ => return Err(NpyError::ShapeMismatch { inner, found })
+        };
+
+        // the shape comes from the file, so it can't be trusted not to overflow
+        let (len, size) = len.and_then(|len| Some((len, len.checked_mul(core::mem::size_of::<T>())?)))
+            .ok_or_else(|| NpyError::InvalidHeader(format!("shape {found:?} is too large")))?;
+
+        if !swap {
+            return Ok(Self::from_reader_sized_with_queue(queue, len, flags, src)?);
+        }
+
+        let ctx = queue.context()?;
+        let flags = flags - (MemFlag::COPY_HOST_PTR | MemFlag::USE_HOST_PTR);
+        let mut this = unsafe { Self::uninit_with_context(&ctx, len, flags)? };
+        let mut remaining = size;
+
+        let mut writer = this.writer_with_queue(queue, DEFAULT_CHUNK_SIZE);
+        let mut chunk = alloc::vec![0u8; DEFAULT_CHUNK_SIZE - DEFAULT_CHUNK_SIZE % T::SCALAR_SIZE];
+
+        while remaining > 0 {
+            let chunk = &mut chunk[..remaining.min(DEFAULT_CHUNK_SIZE - DEFAULT_CHUNK_SIZE % T::SCALAR_SIZE)];
+            src.read_exact(chunk)?;
+            chunk.chunks_exact_mut(T::SCALAR_SIZE).for_each(<[u8]>::reverse);
+            writer.write_all(chunk)?;
+            remaining -= chunk.len();
+        }
+
+        writer.flush()?;
+        drop(writer);
+        Ok(this)
+    }
+
+    /// Saves the buffer as a NumPy ```.npy``` file, in native byte order
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn save_npy<W: ?Sized + Write> (&self, dst: &mut W, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<(), NpyError> {
+        self.save_npy_with_queue(CommandQueue::default(), dst, wait)
+    }
+
+    /// Saves the buffer as a NumPy ```.npy``` file, in native byte order
+    pub fn save_npy_with_queue<W: ?Sized + Write> (&self, queue: &CommandQueue, dst: &mut W, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<(), NpyError> {
+        let mut shape = alloc::vec![self.len()?];
+        T::inner_shape(&mut shape);
+
+        let header = NpyHeader { descr: T::descr(), fortran_order: false, shape };
+        header.write(dst)?;
+
+        queue.marker(wait)?.wait()?;
+        let mut reader = self.reader_with_queue(queue, DEFAULT_CHUNK_SIZE);
+        std::io::copy(&mut reader, dst)?;
+        Ok(())
+    }
+}
+
+/// Checks that ```descr``` describes ```T```'s scalars, returning whether its byte order is the opposite of the native one
+fn check_descr<T: NpyElement> (descr: &str) -> Result<bool, NpyError> {
+    let mismatch = || NpyError::DtypeMismatch { expected: T::descr(), found: descr.to_string() };
+    let mut chars = descr.chars();
+
+    let swap = match chars.next() {
+        Some('<') => cfg!(target_endian = "big") && T::SCALAR_SIZE > 1,
+        Some('>') => cfg!(target_endian = "little") && T::SCALAR_SIZE > 1,
+        Some('|' | '=') => false,
+        _ => return Err(mismatch())
+    };
+
+    if chars.next() != Some(T::KIND) || chars.as_str().parse::<usize>().ok() != Some(T::SCALAR_SIZE) {
+        return Err(mismatch());
+    }
+
+    Ok(swap)
+}
+
+#[inline(always)]
+fn byte_order (size: usize) -> char {
+    match size {
+        1 => '|',
+        _ if cfg!(target_endian = "little") => '<',
+        _ => '>'
+    }
+}
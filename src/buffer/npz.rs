@@ -0,0 +1,106 @@
+#[cfg(test)]
+extern crate std;
+
+use std::io::{Read, Write, Seek};
+use alloc::{string::String, format};
+use zip::{ZipArchive, ZipWriter, CompressionMethod, write::FileOptions, result::ZipError};
+use crate::prelude::{CommandQueue, BaseEvent};
+use super::{MemBuffer, MemFlag, NpyElement, NpyError};
+
+/// NumPy ```.npz``` archive of named arrays, opened for reading. Both stored and compressed (i.e. ```np.savez_compressed```) archives are supported.
+pub struct NpzArchive<R: Read + Seek> {
+    inner: ZipArchive<R>
+}
+
+impl<R: Read + Seek> NpzArchive<R> {
+    #[inline(always)]
+    pub fn new (reader: R) -> Result<Self, NpyError> {
+        Ok(Self { inner: ZipArchive::new(reader)? })
+    }
+
+    /// Returns the names of the arrays inside the archive
+    #[inline(always)]
+    pub fn names (&self) -> impl Iterator<Item = &str> {
+        self.inner.file_names().map(|name| name.strip_suffix(".npy").unwrap_or(name))
+    }
+
+    /// Returns the number of arrays inside the archive
+    #[inline(always)]
+    pub fn len (&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns ```true``` if the archive contains no arrays
+    #[inline(always)]
+    pub fn is_empty (&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Loads the array called ```name``` into a new buffer
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn load<T: NpyElement> (&mut self, name: &str, flags: MemFlag) -> Result<MemBuffer<T>, NpyError> {
+        self.load_with_queue(CommandQueue::default(), name, flags)
+    }
+
+    /// Loads the array called ```name``` into a new buffer, created on the context of ```queue```
+    /// # Errors
+    /// Returns [```NpyError::NotFound```] if the archive doesn't contain an array called ```name```
+    pub fn load_with_queue<T: NpyElement> (&mut self, queue: &CommandQueue, name: &str, flags: MemFlag) -> Result<MemBuffer<T>, NpyError> {
+        let file = format!("{name}.npy");
+        let file = match self.inner.file_names().any(|x| x == file) {
+            true => file.as_str(),
+            false => name
+        };
+
+        let mut file = self.inner.by_name(file).map_err(|e| match e {
+            ZipError::FileNotFound => NpyError::NotFound(String::from(name)),
+            e => NpyError::Zip(e)
+        })?;
+
+        MemBuffer::load_npy_with_queue(queue, flags, &mut file)
+    }
+
+    /// Returns the underlying reader
+    #[inline(always)]
+    pub fn into_inner (self) -> R {
+        self.inner.into_inner()
+    }
+}
+
+/// NumPy ```.npz``` archive of named arrays, opened for writing. Arrays are stored without compression, like ```np.savez```.
+pub struct NpzWriter<W: Write + Seek> {
+    inner: ZipWriter<W>
+}
+
+impl<W: Write + Seek> NpzWriter<W> {
+    #[inline(always)]
+    pub fn new (writer: W) -> Self {
+        Self { inner: ZipWriter::new(writer) }
+    }
+
+    /// Adds ```buffer``` to the archive, as an array called ```name```
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn add<T: NpyElement> (&mut self, name: &str, buffer: &MemBuffer<T>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<(), NpyError> {
+        self.add_with_queue(CommandQueue::default(), name, buffer, wait)
+    }
+
+    /// Adds ```buffer``` to the archive, as an array called ```name```
+    pub fn add_with_queue<T: NpyElement> (&mut self, queue: &CommandQueue, name: &str, buffer: &MemBuffer<T>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<(), NpyError> {
+        // headers are at most a few hundred bytes, so any buffer close to 4 GiB needs zip64
+        let large = buffer.byte_size()? >= (u32::MAX as usize) - 4096;
+        let options = FileOptions::default()
+            .compression_method(CompressionMethod::Stored)
+            .large_file(large);
+
+        self.inner.start_file(format!("{name}.npy"), options)?;
+        buffer.save_npy_with_queue(queue, &mut self.inner, wait)
+    }
+
+    /// Writes the archive's central directory, returning the underlying writer
+    #[inline(always)]
+    pub fn finish (mut self) -> Result<W, NpyError> {
+        Ok(self.inner.finish()?)
+    }
+}
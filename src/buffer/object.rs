@@ -0,0 +1,32 @@
+use opencl_sys::{cl_mem, CL_MEM_OBJECT_BUFFER, CL_MEM_OBJECT_IMAGE2D, CL_MEM_OBJECT_IMAGE3D, CL_MEM_OBJECT_IMAGE2D_ARRAY, CL_MEM_OBJECT_IMAGE1D, CL_MEM_OBJECT_IMAGE1D_ARRAY, CL_MEM_OBJECT_IMAGE1D_BUFFER};
+use super::{MemBuffer, DeviceElement};
+
+/// An OpenCL memory object (i.e. a buffer, an image or a pipe)
+/// # Safety
+/// [```id```](MemObject::id) must return a valid OpenCL memory object, that stays alive for as long as ```self``` is borrowed
+pub unsafe trait MemObject {
+    /// Returns the raw OpenCL id of the memory object
+    fn id (&self) -> cl_mem;
+}
+
+unsafe impl<T: DeviceElement> MemObject for MemBuffer<T> {
+    #[inline(always)]
+    fn id (&self) -> cl_mem {
+        self.0
+    }
+}
+
+/// Type of an OpenCL memory object
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum MemObjectType {
+    Buffer = CL_MEM_OBJECT_BUFFER,
+    Image2D = CL_MEM_OBJECT_IMAGE2D,
+    Image3D = CL_MEM_OBJECT_IMAGE3D,
+    Image2DArray = CL_MEM_OBJECT_IMAGE2D_ARRAY,
+    Image1D = CL_MEM_OBJECT_IMAGE1D,
+    Image1DArray = CL_MEM_OBJECT_IMAGE1D_ARRAY,
+    Image1DBuffer = CL_MEM_OBJECT_IMAGE1D_BUFFER,
+    #[cfg(feature = "cl2")]
+    Pipe = opencl_sys::CL_MEM_OBJECT_PIPE
+}
\ No newline at end of file
@@ -0,0 +1,184 @@
+use core::ops::Neg;
+use alloc::string::String;
+use crate::{prelude::*, kernel::Kernel, event::various::Swap, buffer::{MemFlag, ClType}};
+use super::codegen::ProgramCache;
+
+#[cfg(feature = "def")]
+use core::ops::{Add, Sub, Mul, Div};
+#[cfg(feature = "error-stack")]
+use alloc::format;
+
+lazy_static! {
+    static ref PROGRAMS : ProgramCache = ProgramCache::new();
+}
+
+macro_rules! impl_ops {
+    ($($trait:ident::$fn:ident => $kernel:literal, $scalar_kernel:literal, $desc:literal, $with_queue:ident, $scalar_with_queue:ident, $assign:ident, $assign_with_queue:ident, $assign_scalar:ident, $assign_scalar_with_queue:ident);+ $(;)?) => {
+        impl<T: ClType> MemBuffer<T> {
+            $(
+                #[doc = concat!("Returns a new buffer with the element-wise ", $desc, " of both buffers.")]
+                /// # Errors
+                /// Returns [```Error::InvalidBufferSize```] if the buffers have different lengths
+                pub fn $with_queue (&self, queue: &CommandQueue, rhs: &Self, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Swap<MemBuffer<T>, BaseEvent>> {
+                    let out = self.elementwise_output(queue)?;
+                    let evt = self.binary_into(queue, $kernel, rhs, &out, wait)?;
+                    Ok(evt.swap(out))
+                }
+
+                #[doc = concat!("Returns a new buffer with the element-wise ", $desc, " of the buffer and ```rhs```.")]
+                pub fn $scalar_with_queue (&self, queue: &CommandQueue, rhs: T, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Swap<MemBuffer<T>, BaseEvent>> {
+                    let out = self.elementwise_output(queue)?;
+                    let evt = self.scalar_into(queue, $scalar_kernel, rhs, &out, wait)?;
+                    Ok(evt.swap(out))
+                }
+
+                #[doc = concat!("Stores the element-wise ", $desc, " of both buffers into ```self```.")]
+                /// # Errors
+                /// Returns [```Error::InvalidBufferSize```] if the buffers have different lengths
+                #[cfg(feature = "def")]
+                #[inline(always)]
+                pub fn $assign (&mut self, rhs: &Self, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+                    self.$assign_with_queue(CommandQueue::default(), rhs, wait)
+                }
+
+                #[doc = concat!("Stores the element-wise ", $desc, " of both buffers into ```self```.")]
+                /// # Errors
+                /// Returns [```Error::InvalidBufferSize```] if the buffers have different lengths
+                #[inline(always)]
+                pub fn $assign_with_queue (&mut self, queue: &CommandQueue, rhs: &Self, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+                    self.binary_into(queue, $kernel, rhs, self, wait)
+                }
+
+                #[doc = concat!("Stores the element-wise ", $desc, " of the buffer and ```rhs``` into ```self```.")]
+                #[cfg(feature = "def")]
+                #[inline(always)]
+                pub fn $assign_scalar (&mut self, rhs: T, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+                    self.$assign_scalar_with_queue(CommandQueue::default(), rhs, wait)
+                }
+
+                #[doc = concat!("Stores the element-wise ", $desc, " of the buffer and ```rhs``` into ```self```.")]
+                #[inline(always)]
+                pub fn $assign_scalar_with_queue (&mut self, queue: &CommandQueue, rhs: T, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+                    self.scalar_into(queue, $scalar_kernel, rhs, self, wait)
+                }
+            )+
+        }
+
+        $(
+            #[cfg(feature = "def")]
+            impl<T: ClType> $trait<&MemBuffer<T>> for &MemBuffer<T> {
+                type Output = Result<Swap<MemBuffer<T>, BaseEvent>>;
+
+                #[inline(always)]
+                fn $fn (self, rhs: &MemBuffer<T>) -> Self::Output {
+                    self.$with_queue(CommandQueue::default(), rhs, EMPTY)
+                }
+            }
+
+            #[cfg(feature = "def")]
+            impl<T: ClType> $trait<T> for &MemBuffer<T> {
+                type Output = Result<Swap<MemBuffer<T>, BaseEvent>>;
+
+                #[inline(always)]
+                fn $fn (self, rhs: T) -> Self::Output {
+                    self.$scalar_with_queue(CommandQueue::default(), rhs, EMPTY)
+                }
+            }
+        )+
+    };
+}
+
+impl_ops! {
+    Add::add => "add", "add_scalar", "sum", add_with_queue, add_scalar_with_queue, add_assign, add_assign_with_queue, add_assign_scalar, add_assign_scalar_with_queue;
+    Sub::sub => "sub", "sub_scalar", "difference", sub_with_queue, sub_scalar_with_queue, sub_assign, sub_assign_with_queue, sub_assign_scalar, sub_assign_scalar_with_queue;
+    Mul::mul => "mul", "mul_scalar", "product", mul_with_queue, mul_scalar_with_queue, mul_assign, mul_assign_with_queue, mul_assign_scalar, mul_assign_scalar_with_queue;
+    // integer division by zero is undefined behaviour on the device, and won't panic
+    Div::div => "div", "div_scalar", "quotient", div_with_queue, div_scalar_with_queue, div_assign, div_assign_with_queue, div_assign_scalar, div_assign_scalar_with_queue;
+}
+
+impl<T: ClType + Neg<Output = T>> MemBuffer<T> {
+    /// Returns a new buffer with the negation of every element of the buffer.
+    pub fn neg_with_queue (&self, queue: &CommandQueue, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Swap<MemBuffer<T>, BaseEvent>> {
+        let out = self.elementwise_output(queue)?;
+        let evt = self.neg_into(queue, &out, wait)?;
+        Ok(evt.swap(out))
+    }
+
+    /// Negates every element of the buffer in place.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn neg_assign (&mut self, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        self.neg_assign_with_queue(CommandQueue::default(), wait)
+    }
+
+    /// Negates every element of the buffer in place.
+    #[inline(always)]
+    pub fn neg_assign_with_queue (&mut self, queue: &CommandQueue, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        self.neg_into(queue, self, wait)
+    }
+
+    fn neg_into (&self, queue: &CommandQueue, out: &Self, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        let mut kernel = ops_kernel::<T>(queue, "neg")?;
+        kernel.set_mem_arg(0, self)?;
+        kernel.set_mem_arg(1, out)?;
+        enqueue_elementwise(queue, &mut kernel, self.len()?, wait)
+    }
+}
+
+#[cfg(feature = "def")]
+impl<T: ClType + Neg<Output = T>> Neg for &MemBuffer<T> {
+    type Output = Result<Swap<MemBuffer<T>, BaseEvent>>;
+
+    #[inline(always)]
+    fn neg (self) -> Self::Output {
+        self.neg_with_queue(CommandQueue::default(), EMPTY)
+    }
+}
+
+impl<T: ClType> MemBuffer<T> {
+    #[inline(always)]
+    fn elementwise_output (&self, queue: &CommandQueue) -> Result<MemBuffer<T>> {
+        unsafe { MemBuffer::<T>::uninit_with_context(&queue.context()?, self.len()?, MemFlag::READ_WRITE) }
+    }
+
+    /// Enqueues ```kernel``` with ```self``` and ```rhs``` as its operands, storing the result into ```out``` (which may be ```self```)
+    fn binary_into (&self, queue: &CommandQueue, kernel: &str, rhs: &Self, out: &Self, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        let (len, rhs_len) = (self.len()?, rhs.len()?);
+        if len != rhs_len {
+            #[cfg(feature = "error-stack")]
+            return Err(error_stack::Report::new(Error::InvalidBufferSize).attach_printable(format!("left-hand side has {len} elements, but right-hand side has {rhs_len}")));
+            #[cfg(not(feature = "error-stack"))]
+            return Err(Error::InvalidBufferSize);
+        }
+
+        let mut kernel = ops_kernel::<T>(queue, kernel)?;
+        kernel.set_mem_arg(0, self)?;
+        kernel.set_mem_arg(1, rhs)?;
+        kernel.set_mem_arg(2, out)?;
+        enqueue_elementwise(queue, &mut kernel, len, wait)
+    }
+
+    /// Enqueues ```kernel``` with ```self``` and the scalar ```rhs``` as its operands, storing the result into ```out``` (which may be ```self```)
+    fn scalar_into (&self, queue: &CommandQueue, kernel: &str, rhs: T, out: &Self, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        let mut kernel = ops_kernel::<T>(queue, kernel)?;
+        kernel.set_mem_arg(0, self)?;
+        kernel.set_arg(1, rhs)?;
+        kernel.set_mem_arg(2, out)?;
+        enqueue_elementwise(queue, &mut kernel, self.len()?, wait)
+    }
+}
+
+fn ops_kernel<T: ClType> (queue: &CommandQueue, name: &str) -> Result<Kernel> {
+    let program = PROGRAMS.get_or_build::<T>(&queue.context()?, "", || String::from(include_str!("ops.ocl")))?;
+    unsafe { Kernel::new_unchecked(&program, name) }
+}
+
+/// Enqueues ```kernel``` with one work-item per element
+pub(crate) fn enqueue_elementwise (queue: &CommandQueue, kernel: &mut Kernel, len: usize, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+    // empty ranges aren't valid work sizes
+    if len == 0 {
+        return queue.marker(wait)
+    }
+
+    kernel.enqueue_with_queue(queue, &[len], None, wait)
+}
@@ -0,0 +1,276 @@
+use core::{marker::PhantomData, mem::ManuallyDrop, ops::Deref, ptr::addr_of, fmt::Debug};
+use alloc::{vec::Vec, collections::BTreeMap};
+use opencl_sys::{clCreateSubBuffer, clRetainMemObject, CL_BUFFER_CREATE_TYPE_REGION};
+use parking_lot::Mutex;
+use crate::prelude::{Context, Result, Error, Event, BaseEvent};
+use super::{MemBuffer, MemFlag, DeviceElement, WriteSlice};
+
+/// Smallest size class, in bytes
+const MIN_CLASS: usize = 256;
+
+/// Caching allocator for temporary buffers of a single context.
+///
+/// Allocations are bucketed by flags and size class (the requested byte size, rounded up to the next power of two).
+/// Buffers handed out by the pool return to it when dropped, and are reused by later allocations of the same bucket,
+/// as long as the pool doesn't exceed its maximum cached byte count.
+pub struct BufferPool {
+    ctx: Context,
+    inner: Mutex<PoolInner>
+}
+
+struct PoolInner {
+    buckets: BTreeMap<(u64, usize), Vec<MemBuffer<u8>>>,
+    max_cached: usize,
+    stats: PoolStats
+}
+
+/// Usage statistics of a [```BufferPool```]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PoolStats {
+    /// Number of allocations served from the cache
+    pub hits: u64,
+    /// Number of allocations that required a new OpenCL buffer
+    pub misses: u64,
+    /// Bytes currently held by the cache
+    pub cached_bytes: usize,
+    /// Buffers currently held by the cache
+    pub cached_buffers: usize
+}
+
+impl BufferPool {
+    /// Creates a new pool for the default context, which caches up to ```max_cached``` bytes
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn new (max_cached: usize) -> Self {
+        Self::with_context(Context::default(), max_cached)
+    }
+
+    /// Creates a new pool for ```ctx```, which caches up to ```max_cached``` bytes
+    #[inline(always)]
+    pub fn with_context (ctx: &Context, max_cached: usize) -> Self {
+        Self {
+            ctx: ctx.clone(),
+            inner: Mutex::new(PoolInner { buckets: BTreeMap::new(), max_cached, stats: PoolStats::default() })
+        }
+    }
+
+    #[inline(always)]
+    pub fn context (&self) -> &Context {
+        &self.ctx
+    }
+
+    /// Returns the maximum number of bytes the pool will cache
+    #[inline(always)]
+    pub fn max_cached (&self) -> usize {
+        self.inner.lock().max_cached
+    }
+
+    /// Sets the maximum number of bytes the pool will cache, releasing cached buffers if needed
+    pub fn set_max_cached (&self, max_cached: usize) {
+        let mut inner = self.inner.lock();
+        inner.max_cached = max_cached;
+
+        while inner.stats.cached_bytes > max_cached {
+            let (&key, _) = inner.buckets.iter().next_back().unwrap();
+            let bucket = inner.buckets.get_mut(&key).unwrap();
+            bucket.pop();
+            if bucket.is_empty() {
+                inner.buckets.remove(&key);
+            }
+
+            inner.stats.cached_bytes -= key.1;
+            inner.stats.cached_buffers -= 1;
+        }
+    }
+
+    /// Returns the pool's usage statistics
+    #[inline(always)]
+    pub fn stats (&self) -> PoolStats {
+        self.inner.lock().stats
+    }
+
+    /// Releases every cached buffer. Buffers currently in use will still return to the pool when dropped
+    pub fn trim (&self) {
+        let buckets = {
+            let mut inner = self.inner.lock();
+            inner.stats.cached_bytes = 0;
+            inner.stats.cached_buffers = 0;
+            core::mem::take(&mut inner.buckets)
+        };
+
+        drop(buckets)
+    }
+
+    /// Returns a buffer of ```len``` elements, reusing a cached allocation if possible.
+    ///
+    /// The allocation returns to the pool as soon as the buffer is dropped, after waiting for the events registered with [```PooledBuffer::track```].
+    /// Commands that use the buffer must either be tracked, or be enqueued on the same in-order queue as every later use of the pool,
+    /// since the allocation may otherwise be handed out again while they're still running.
+    /// # Safety
+    /// The contents of the buffer are uninitialized, and may contain data from previous uses of the allocation
+    pub unsafe fn uninit<T: DeviceElement> (&self, len: usize, flags: MemFlag) -> Result<PooledBuffer<'_, T>> {
+        let flags = flags - (MemFlag::USE_HOST_PTR | MemFlag::COPY_HOST_PTR);
+        let size = len.checked_mul(core::mem::size_of::<T>()).expect("Buffer size overflow");
+        let class = size.max(MIN_CLASS).checked_next_power_of_two().expect("Buffer size overflow");
+        let key = (flags.bits(), class);
+
+        let cached = {
+            let mut inner = self.inner.lock();
+            match inner.buckets.get_mut(&key).and_then(Vec::pop) {
+                Some(parent) => {
+                    if inner.buckets.get(&key).is_some_and(Vec::is_empty) {
+                        inner.buckets.remove(&key);
+                    }
+
+                    inner.stats.hits += 1;
+                    inner.stats.cached_bytes -= class;
+                    inner.stats.cached_buffers -= 1;
+                    Some(parent)
+                },
+
+                None => {
+                    inner.stats.misses += 1;
+                    None
+                }
+            }
+        };
+
+        let parent = match cached {
+            Some(parent) => parent,
+            None => MemBuffer::<u8>::uninit_with_context(&self.ctx, class, flags)?
+        };
+
+        let buffer = Self::view(&parent, size)?;
+        Ok(PooledBuffer { pool: self, key, parent: ManuallyDrop::new(parent), buffer: ManuallyDrop::new(buffer), pending: Vec::new() })
+    }
+
+    /// Returns a buffer that covers the first ```size``` bytes of ```parent```
+    unsafe fn view<T: DeviceElement> (parent: &MemBuffer<u8>, size: usize) -> Result<MemBuffer<T>> {
+        if size == parent.byte_size()? {
+            tri_panic!(clRetainMemObject(parent.0));
+            return Ok(MemBuffer(parent.0, parking_lot::lock_api::RawRwLock::INIT, PhantomData))
+        }
+
+        let region = opencl_sys::cl_buffer_region {
+            origin: 0,
+            size
+        };
+
+        // flags are inherited from the parent
+        let mut err = 0;
+        let id = clCreateSubBuffer(parent.0, 0, CL_BUFFER_CREATE_TYPE_REGION, addr_of!(region).cast(), &mut err);
+
+        if err == 0 {
+            return Ok(MemBuffer(id, parking_lot::lock_api::RawRwLock::INIT, PhantomData));
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "error-stack")] {
+                let err = Error::from(err);
+                let report = error_stack::Report::new(err);
+
+                let report = match err {
+                    Error::InvalidBufferSize => report.attach_printable("size is zero"),
+                    Error::OutOfHostMemory => report.attach_printable("failure to allocate resources required by the OpenCL implementation on the host"),
+                    Error::OutOfResources => report.attach_printable("failure to allocate resources required by the OpenCL implementation on the device"),
+                    _ => report
+                };
+
+                Err(report)
+            } else {
+                Err(Error::from(err))
+            }
+        }
+    }
+
+    fn give_back (&self, key: (u64, usize), parent: MemBuffer<u8>, pending: Vec<BaseEvent>) {
+        // commands that may still be using the allocation must complete before it's reused.
+        // if any of them failed, the allocation is released instead
+        if !pending.is_empty() && BaseEvent::wait_all(pending).is_err() {
+            drop(parent);
+            return;
+        }
+
+        let mut inner = self.inner.lock();
+        if inner.stats.cached_bytes + key.1 > inner.max_cached {
+            drop(inner);
+            drop(parent);
+            return;
+        }
+
+        inner.stats.cached_bytes += key.1;
+        inner.stats.cached_buffers += 1;
+        inner.buckets.entry(key).or_default().push(parent);
+    }
+}
+
+impl Debug for BufferPool {
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BufferPool")
+            .field("ctx", &self.ctx)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+/// Buffer handed out by a [```BufferPool```]. Its allocation returns to the pool when dropped.
+///
+/// The buffer derefs into a shared [```MemBuffer```], and is mutated through [```view_mut```](PooledBuffer::view_mut),
+/// so that it can't be moved out of the guard while its allocation returns to the pool.
+pub struct PooledBuffer<'a, T: DeviceElement> {
+    pool: &'a BufferPool,
+    key: (u64, usize),
+    parent: ManuallyDrop<MemBuffer<u8>>,
+    buffer: ManuallyDrop<MemBuffer<T>>,
+    pending: Vec<BaseEvent>
+}
+
+impl<T: DeviceElement> PooledBuffer<'_, T> {
+    /// Returns the pool this buffer belongs to
+    #[inline(always)]
+    pub fn pool (&self) -> &BufferPool {
+        self.pool
+    }
+
+    /// Returns a mutable view of the buffer
+    #[inline(always)]
+    pub fn view_mut (&mut self) -> WriteSlice<'_, T> {
+        unsafe {
+            tri_panic!(clRetainMemObject(self.buffer.0));
+            WriteSlice::from_id(self.buffer.0)
+        }
+    }
+
+    /// Registers the event of a command that uses the buffer. When the buffer is dropped, it waits for every registered event to complete before returning the allocation to the pool.
+    #[inline(always)]
+    pub fn track (&mut self, evt: impl AsRef<BaseEvent>) {
+        self.pending.push(evt.as_ref().clone())
+    }
+}
+
+impl<T: DeviceElement> Deref for PooledBuffer<'_, T> {
+    type Target = MemBuffer<T>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.buffer
+    }
+}
+
+impl<T: DeviceElement> Debug for PooledBuffer<'_, T> where MemBuffer<T>: Debug {
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&*self.buffer, f)
+    }
+}
+
+impl<T: DeviceElement> Drop for PooledBuffer<'_, T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        unsafe {
+            ManuallyDrop::drop(&mut self.buffer);
+            self.pool.give_back(self.key, ManuallyDrop::take(&mut self.parent), core::mem::take(&mut self.pending));
+        }
+    }
+}
@@ -6,7 +6,7 @@ use core::{sync::atomic::{AtomicU64, Ordering}};
 use std::{time::{SystemTime}};
 use alloc::vec::Vec;
 use parking_lot::{Mutex};
-use crate::{prelude::*, kernel::Kernel, event::various::Swap, buffer::MemFlag};
+use crate::{prelude::*, kernel::Kernel, event::various::Swap, buffer::{MemFlag, DeviceElement}};
 
 static UNIQUIFIER : AtomicU64 = AtomicU64::new(8682522807148012);
 const FAST_MUL : u64 = 0x5DEECE66D;
@@ -176,7 +176,7 @@ impl FastRng {
     }
 
     #[inline]
-    fn inner_random<T: Copy + Unpin> (&self, queue: &CommandQueue, kernel: &mut Kernel, out: &MemBuffer<T>, offset: usize, len: usize, wgs: usize, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+    fn inner_random<T: DeviceElement> (&self, queue: &CommandQueue, kernel: &mut Kernel, out: &MemBuffer<T>, offset: usize, len: usize, wgs: usize, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
         kernel.set_arg(0, len)?;
         kernel.set_arg(1, offset)?;
         kernel.set_mem_arg(2, &self.seeds)?;
@@ -185,7 +185,7 @@ impl FastRng {
     }
 
     #[inline]
-    fn inner_random_float<T: Copy + Unpin> (&self, queue: &CommandQueue, kernel: &mut Kernel, out: &MemBuffer<T>, min: T, max: T, offset: usize, len: usize, wgs: usize, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+    fn inner_random_float<T: DeviceElement> (&self, queue: &CommandQueue, kernel: &mut Kernel, out: &MemBuffer<T>, min: T, max: T, offset: usize, len: usize, wgs: usize, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
         kernel.set_arg(0, len)?;
         kernel.set_arg(1, min)?;
         kernel.set_arg(2, max)?;
@@ -0,0 +1,118 @@
+use crate::prelude::{Result, Error};
+
+#[cfg(feature = "error-stack")]
+use alloc::format;
+
+/// Position and layout of a rectangular (2D or 3D) region inside a buffer or host slice, measured in elements.
+/// 
+/// A pitch of zero is computed from the region being copied, as if the memory was tightly packed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct RectLayout {
+    /// Offset of the region as ```[column, row, slice]```
+    pub origin: [usize; 3],
+    /// Number of elements between the start of two consecutive rows
+    pub row_pitch: usize,
+    /// Number of elements between the start of two consecutive slices
+    pub slice_pitch: usize
+}
+
+impl RectLayout {
+    #[inline(always)]
+    pub const fn new (origin: [usize; 3], row_pitch: usize, slice_pitch: usize) -> Self {
+        Self { origin, row_pitch, slice_pitch }
+    }
+
+    /// Layout of a tightly packed region starting at ```origin```
+    #[inline(always)]
+    pub const fn packed (origin: [usize; 3]) -> Self {
+        Self::new(origin, 0, 0)
+    }
+
+    /// Layout of a 2D region starting at column ```x``` and row ```y``` of a row-major matrix with ```row_pitch``` elements per row
+    #[inline(always)]
+    pub const fn new_2d (x: usize, y: usize, row_pitch: usize) -> Self {
+        Self::new([x, y, 0], row_pitch, 0)
+    }
+
+    /// Returns the row and slice pitches used when copying ```region```, in elements.
+    /// # Errors
+    /// Returns [```Error::InvalidValue```] if the pitches are too small to hold ```region```.
+    pub fn pitches (&self, region: [usize; 3]) -> Result<(usize, usize)> {
+        let row_pitch = match self.row_pitch {
+            0 => region[0],
+            x => x
+        };
+
+        let min_slice = row_pitch.checked_mul(region[1]).ok_or_else(overflow)?;
+        let slice_pitch = match self.slice_pitch {
+            0 => min_slice,
+            x => x
+        };
+
+        if row_pitch < region[0] || slice_pitch < min_slice {
+            #[cfg(feature = "error-stack")]
+            return Err(error_stack::Report::new(Error::InvalidValue).attach_printable(format!("pitches ({row_pitch}, {slice_pitch}) are too small for a region of {region:?} elements")));
+            #[cfg(not(feature = "error-stack"))]
+            return Err(Error::InvalidValue);
+        }
+
+        Ok((row_pitch, slice_pitch))
+    }
+
+    /// Returns the minimum number of elements the underlying memory must have to fit ```region``` at this layout.
+    pub fn required_len (&self, region: [usize; 3]) -> Result<usize> {
+        if region.contains(&0) {
+            return Ok(0)
+        }
+
+        let (row_pitch, slice_pitch) = self.pitches(region)?;
+        let [x, y, z] = self.origin;
+        
+        let last_slice = z.checked_add(region[2] - 1).and_then(|z| z.checked_mul(slice_pitch));
+        let last_row = y.checked_add(region[1] - 1).and_then(|y| y.checked_mul(row_pitch));
+        let last_col = x.checked_add(region[0]);
+
+        last_slice.zip(last_row).zip(last_col)
+            .and_then(|((z, y), x)| z.checked_add(y)?.checked_add(x))
+            .ok_or_else(overflow)
+    }
+
+    /// Returns the origin, row pitch and slice pitch in bytes, as expected by OpenCL.
+    pub(crate) fn to_bytes<T> (&self, region: [usize; 3]) -> Result<([usize; 3], usize, usize)> {
+        let size = core::mem::size_of::<T>();
+        let (row_pitch, slice_pitch) = self.pitches(region)?;
+        
+        let origin = self.origin[0].checked_mul(size).ok_or_else(overflow)?;
+        let row_pitch = row_pitch.checked_mul(size).ok_or_else(overflow)?;
+        let slice_pitch = slice_pitch.checked_mul(size).ok_or_else(overflow)?;
+        Ok(([origin, self.origin[1], self.origin[2]], row_pitch, slice_pitch))
+    }
+
+    /// Checks that a host slice of ```len``` elements can hold ```region``` at this layout.
+    pub(crate) fn check_host (&self, region: [usize; 3], len: usize) -> Result<()> {
+        let required = self.required_len(region)?;
+        if required > len {
+            #[cfg(feature = "error-stack")]
+            return Err(error_stack::Report::new(Error::InvalidValue).attach_printable(format!("host slice has {len} elements, but the region requires at least {required}")));
+            #[cfg(not(feature = "error-stack"))]
+            return Err(Error::InvalidValue);
+        }
+
+        Ok(())
+    }
+}
+
+/// Converts a region measured in elements into the byte region expected by OpenCL
+#[inline]
+pub(crate) fn region_bytes<T> (region: [usize; 3]) -> Result<[usize; 3]> {
+    let width = region[0].checked_mul(core::mem::size_of::<T>()).ok_or_else(overflow)?;
+    Ok([width, region[1], region[2]])
+}
+
+#[inline(always)]
+fn overflow () -> crate::error::ErrorCL {
+    #[cfg(feature = "error-stack")]
+    return error_stack::Report::new(Error::InvalidValue).attach_printable("integer overflow computing the size of the region");
+    #[cfg(not(feature = "error-stack"))]
+    return Error::InvalidValue;
+}
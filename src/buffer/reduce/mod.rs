@@ -0,0 +1,167 @@
+use core::mem::MaybeUninit;
+use alloc::{boxed::Box, string::String};
+use crate::{prelude::*, kernel::Kernel, event::various::Map, buffer::{MemFlag, ClType}};
+use super::codegen::{ProgramCache, pow2_local_size};
+
+lazy_static! {
+    static ref PROGRAMS : ProgramCache = ProgramCache::new();
+}
+
+impl<T: ClType> MemBuffer<T> {
+    /// Returns the sum of all the elements of the buffer. Integer overflow wraps around.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn sum (&self, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Map<T, BaseEvent, impl FnOnce(()) -> T>> {
+        self.sum_with_queue(CommandQueue::default(), wait)
+    }
+
+    /// Returns the sum of all the elements of the buffer. Integer overflow wraps around.
+    #[inline(always)]
+    pub fn sum_with_queue (&self, queue: &CommandQueue, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Map<T, BaseEvent, impl FnOnce(()) -> T>> {
+        let (out, evt) = self.reduce_with_queue(queue, "reduce_sum", wait)?;
+        read_result(queue, &out, evt, |[x]| x)
+    }
+
+    /// Returns the product of all the elements of the buffer. Integer overflow wraps around.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn product (&self, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Map<T, BaseEvent, impl FnOnce(()) -> T>> {
+        self.product_with_queue(CommandQueue::default(), wait)
+    }
+
+    /// Returns the product of all the elements of the buffer. Integer overflow wraps around.
+    #[inline(always)]
+    pub fn product_with_queue (&self, queue: &CommandQueue, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Map<T, BaseEvent, impl FnOnce(()) -> T>> {
+        let (out, evt) = self.reduce_with_queue(queue, "reduce_product", wait)?;
+        read_result(queue, &out, evt, |[x]| x)
+    }
+
+    /// Returns the smallest element of the buffer. NaN values are skipped, unless they're the first element.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn min (&self, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Map<T, BaseEvent, impl FnOnce(()) -> T>> {
+        self.min_with_queue(CommandQueue::default(), wait)
+    }
+
+    /// Returns the smallest element of the buffer. NaN values are skipped, unless they're the first element.
+    #[inline(always)]
+    pub fn min_with_queue (&self, queue: &CommandQueue, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Map<T, BaseEvent, impl FnOnce(()) -> T>> {
+        let (out, evt) = self.reduce_with_queue(queue, "reduce_min", wait)?;
+        read_result(queue, &out, evt, |[x]| x)
+    }
+
+    /// Returns the largest element of the buffer. NaN values are skipped, unless they're the first element.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn max (&self, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Map<T, BaseEvent, impl FnOnce(()) -> T>> {
+        self.max_with_queue(CommandQueue::default(), wait)
+    }
+
+    /// Returns the largest element of the buffer. NaN values are skipped, unless they're the first element.
+    #[inline(always)]
+    pub fn max_with_queue (&self, queue: &CommandQueue, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Map<T, BaseEvent, impl FnOnce(()) -> T>> {
+        let (out, evt) = self.reduce_with_queue(queue, "reduce_max", wait)?;
+        read_result(queue, &out, evt, |[x]| x)
+    }
+
+    /// Returns the smallest and largest elements of the buffer, in a single pass.
+    #[cfg(feature = "def")]
+    #[allow(clippy::type_complexity)]
+    #[inline(always)]
+    pub fn min_max (&self, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Map<(T, T), BaseEvent, impl FnOnce(()) -> (T, T)>> {
+        self.min_max_with_queue(CommandQueue::default(), wait)
+    }
+
+    /// Returns the smallest and largest elements of the buffer, in a single pass.
+    #[allow(clippy::type_complexity)]
+    pub fn min_max_with_queue (&self, queue: &CommandQueue, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Map<(T, T), BaseEvent, impl FnOnce(()) -> (T, T)>> {
+        let ctx = queue.context()?;
+        let mut kernel = reduce_kernel::<T>(&ctx, "reduce_min_max")?;
+        let (len, wgs, groups) = self.reduce_dims(queue)?;
+
+        // minimums on the first half, maximums on the second one
+        let partial = unsafe { MemBuffer::<T>::uninit_with_context(&ctx, 2 * groups, MemFlag::READ_WRITE)? };
+        kernel.set_arg(0, len as u64)?;
+        kernel.set_mem_arg(1, self)?;
+        kernel.set_arg(2, 0u64)?;
+        kernel.set_mem_arg(3, &partial)?;
+        kernel.set_arg(4, groups as u64)?;
+        kernel.alloc_arg::<T>(5, wgs)?;
+        kernel.alloc_arg::<T>(6, wgs)?;
+        let mut evt = kernel.enqueue_with_queue(queue, &[groups * wgs], Some(&[wgs]), wait)?;
+
+        let mut out = partial;
+        if groups > 1 {
+            let result = unsafe { MemBuffer::<T>::uninit_with_context(&ctx, 2, MemFlag::READ_WRITE)? };
+            kernel.set_arg(0, groups as u64)?;
+            kernel.set_mem_arg(1, &out)?;
+            kernel.set_arg(2, groups as u64)?;
+            kernel.set_mem_arg(3, &result)?;
+            kernel.set_arg(4, 1u64)?;
+            evt = kernel.enqueue_with_queue(queue, &[wgs], Some(&[wgs]), [evt])?;
+            out = result;
+        }
+
+        read_result(queue, &out, evt, |[min, max]| (min, max))
+    }
+
+    /// Enqueues the reduction of the buffer with the kernel ```name```, returning the buffer that will contain the result
+    fn reduce_with_queue (&self, queue: &CommandQueue, name: &str, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<(MemBuffer<T>, BaseEvent)> {
+        let ctx = queue.context()?;
+        let mut kernel = reduce_kernel::<T>(&ctx, name)?;
+        let (len, wgs, groups) = self.reduce_dims(queue)?;
+
+        let partial = unsafe { MemBuffer::<T>::uninit_with_context(&ctx, groups, MemFlag::READ_WRITE)? };
+        kernel.set_arg(0, len as u64)?;
+        kernel.set_mem_arg(1, self)?;
+        kernel.set_mem_arg(2, &partial)?;
+        kernel.alloc_arg::<T>(3, wgs)?;
+        let evt = kernel.enqueue_with_queue(queue, &[groups * wgs], Some(&[wgs]), wait)?;
+
+        if groups == 1 {
+            return Ok((partial, evt))
+        }
+
+        // there are at most `wgs` partial results, so a single work-group can finish the reduction.
+        // OpenCL keeps `partial` alive until the kernel that uses it completes.
+        let out = unsafe { MemBuffer::<T>::uninit_with_context(&ctx, 1, MemFlag::READ_WRITE)? };
+        kernel.set_arg(0, groups as u64)?;
+        kernel.set_mem_arg(1, &partial)?;
+        kernel.set_mem_arg(2, &out)?;
+        let evt = kernel.enqueue_with_queue(queue, &[wgs], Some(&[wgs]), [evt])?;
+        Ok((out, evt))
+    }
+
+    /// Returns the buffer's length, the local size and the number of work-groups of the first pass
+    fn reduce_dims (&self, queue: &CommandQueue) -> Result<(usize, usize, usize)> {
+        let len = self.len()?;
+        // two scratch arrays are needed by `min_max`
+        let wgs = pow2_local_size::<T>(queue, 2)?;
+        let groups = len.div_ceil(wgs).min(wgs);
+        Ok((len, wgs, groups))
+    }
+}
+
+#[inline(always)]
+fn reduce_kernel<T: ClType> (ctx: &Context, name: &str) -> Result<Kernel> {
+    let program = PROGRAMS.get_or_build::<T>(ctx, "", || String::from(include_str!("reduce.ocl")))?;
+    unsafe { Kernel::new_unchecked(&program, name) }
+}
+
+/// Reads the contents of ```out``` once ```evt``` completes
+fn read_result<T: ClType, O, const N: usize> (queue: &CommandQueue, out: &MemBuffer<T>, evt: BaseEvent, f: impl Unpin + FnOnce([T; N]) -> O) -> Result<Map<O, BaseEvent, impl FnOnce(()) -> O>> {
+    debug_assert_eq!(out.len()?, N);
+
+    // if the event is dropped before completion, the result is leaked instead of being written into freed memory
+    let ptr = Box::into_raw(Box::new(MaybeUninit::<[T; N]>::uninit()));
+    let read = unsafe { out.read_into_ptr_with_queue(queue, .., ptr.cast(), [evt]) };
+    let read = match read {
+        Ok(read) => read.as_ref().clone(),
+        Err(e) => unsafe {
+            drop(Box::from_raw(ptr));
+            return Err(e)
+        }
+    };
+
+    Ok(read.map(move |_| f(unsafe { *Box::from_raw(ptr).assume_init() })))
+}
@@ -0,0 +1,131 @@
+use alloc::{vec::Vec, format};
+use crate::{prelude::*, kernel::Kernel, event::various::Swap, buffer::{MemFlag, ClType}};
+use super::codegen::{ProgramCache, pow2_local_size};
+
+lazy_static! {
+    static ref PROGRAMS : ProgramCache = ProgramCache::new();
+}
+
+/// Associative operator used by a scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScanOp<'a> {
+    Add,
+    Mul,
+    Min,
+    Max,
+    /// OpenCL C expression that combines the values ```a``` and ```b``` (of type ```T```), where ```a``` precedes ```b``` in the buffer.
+    /// It must be associative, but it doesn't need to be commutative (i.e. ```"a + 2 * b"``` is not a valid operator, but ```"max(a, b)"``` is).
+    Custom(&'a str)
+}
+
+impl ScanOp<'_> {
+    /// Returns the OpenCL C expression of the operator
+    #[inline(always)]
+    pub fn expr (&self) -> &str {
+        match self {
+            Self::Add => "a + b",
+            Self::Mul => "a * b",
+            Self::Min => "b < a ? b : a",
+            Self::Max => "b > a ? b : a",
+            Self::Custom(expr) => expr
+        }
+    }
+}
+
+impl<T: ClType> MemBuffer<T> {
+    /// Returns a new buffer where every element is the result of combining all the elements of this buffer up to (and including) it.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn inclusive_scan (&self, op: ScanOp<'_>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Swap<MemBuffer<T>, BaseEvent>> {
+        self.inclusive_scan_with_queue(CommandQueue::default(), op, wait)
+    }
+
+    /// Returns a new buffer where every element is the result of combining all the elements of this buffer up to (and including) it.
+    pub fn inclusive_scan_with_queue (&self, queue: &CommandQueue, op: ScanOp<'_>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Swap<MemBuffer<T>, BaseEvent>> {
+        let mut kernels = ScanKernels::new::<T>(queue, op)?;
+        let len = self.len()?;
+        let out = unsafe { MemBuffer::<T>::uninit_with_context(&kernels.ctx, len, MemFlag::READ_WRITE)? };
+
+        let wait = wait.into_iter().map(|x| x.as_ref().clone()).collect::<Vec<_>>();
+        let evt = kernels.scan_into(queue, self, &out, len, wait)?;
+        Ok(evt.swap(out))
+    }
+
+    /// Returns a new buffer where every element is the result of combining all the elements of this buffer before it, and where the first element is ```identity```.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn exclusive_scan (&self, op: ScanOp<'_>, identity: T, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Swap<MemBuffer<T>, BaseEvent>> {
+        self.exclusive_scan_with_queue(CommandQueue::default(), op, identity, wait)
+    }
+
+    /// Returns a new buffer where every element is the result of combining all the elements of this buffer before it, and where the first element is ```identity```.
+    #[inline(always)]
+    pub fn exclusive_scan_with_queue (&self, queue: &CommandQueue, op: ScanOp<'_>, identity: T, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Swap<MemBuffer<T>, BaseEvent>> {
+        let (out, evt) = self.exclusive_scan_inner(queue, op, identity, wait)?;
+        Ok(evt.swap(out))
+    }
+
+    /// Enqueues an exclusive scan, returning the output buffer and the event that completes it
+    pub(crate) fn exclusive_scan_inner (&self, queue: &CommandQueue, op: ScanOp<'_>, identity: T, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<(MemBuffer<T>, BaseEvent)> {
+        let mut kernels = ScanKernels::new::<T>(queue, op)?;
+        let len = self.len()?;
+        let inclusive = unsafe { MemBuffer::<T>::uninit_with_context(&kernels.ctx, len, MemFlag::READ_WRITE)? };
+        let out = unsafe { MemBuffer::<T>::uninit_with_context(&kernels.ctx, len, MemFlag::READ_WRITE)? };
+
+        let wait = wait.into_iter().map(|x| x.as_ref().clone()).collect::<Vec<_>>();
+        let evt = kernels.scan_into(queue, self, &inclusive, len, wait)?;
+
+        let mut shift = unsafe { Kernel::new_unchecked(&kernels.program, "shift_right")? };
+        shift.set_arg(0, len as u64)?;
+        shift.set_mem_arg(1, &inclusive)?;
+        shift.set_mem_arg(2, &out)?;
+        shift.set_arg(3, identity)?;
+        let evt = shift.enqueue_with_queue(queue, &[len.next_multiple_of(kernels.wgs)], Some(&[kernels.wgs]), [evt])?;
+        Ok((out, evt))
+    }
+}
+
+struct ScanKernels {
+    ctx: Context,
+    program: Program,
+    blocks: Kernel,
+    offsets: Kernel,
+    wgs: usize
+}
+
+impl ScanKernels {
+    fn new<T: ClType> (queue: &CommandQueue, op: ScanOp<'_>) -> Result<Self> {
+        let ctx = queue.context()?;
+        let program = PROGRAMS.get_or_build::<T>(&ctx, op.expr(), || format!("#define OP(a, b) ({})\n{}", op.expr(), include_str!("scan.ocl")))?;
+        let blocks = unsafe { Kernel::new_unchecked(&program, "scan_blocks")? };
+        let offsets = unsafe { Kernel::new_unchecked(&program, "add_offsets")? };
+        let wgs = pow2_local_size::<T>(queue, 1)?;
+        Ok(Self { ctx, program, blocks, offsets, wgs })
+    }
+
+    /// Enqueues the inclusive scan of the first ```len``` elements of ```input``` into ```out```, which may be the same buffer
+    fn scan_into<T: ClType> (&mut self, queue: &CommandQueue, input: &MemBuffer<T>, out: &MemBuffer<T>, len: usize, wait: Vec<BaseEvent>) -> Result<BaseEvent> {
+        let wgs = self.wgs;
+        let groups = len.div_ceil(wgs);
+        let sums = unsafe { MemBuffer::<T>::uninit_with_context(&self.ctx, groups, MemFlag::READ_WRITE)? };
+
+        self.blocks.set_arg(0, len as u64)?;
+        self.blocks.set_mem_arg(1, input)?;
+        self.blocks.set_mem_arg(2, out)?;
+        self.blocks.set_mem_arg(3, &sums)?;
+        self.blocks.alloc_arg::<T>(4, wgs)?;
+        let evt = self.blocks.enqueue_with_queue(queue, &[groups * wgs], Some(&[wgs]), wait)?;
+
+        if groups == 1 {
+            return Ok(evt)
+        }
+
+        // scan the totals of the blocks in place, and add them back.
+        // OpenCL keeps `sums` alive until the kernels that use it complete.
+        let evt = self.scan_into(queue, &sums, &sums, groups, alloc::vec![evt])?;
+        self.offsets.set_arg(0, len as u64)?;
+        self.offsets.set_mem_arg(1, out)?;
+        self.offsets.set_mem_arg(2, &sums)?;
+        self.offsets.enqueue_with_queue(queue, &[groups * wgs], Some(&[wgs]), [evt])
+    }
+}
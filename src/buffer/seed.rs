@@ -0,0 +1,230 @@
+use core::marker::PhantomData;
+use alloc::vec::Vec;
+use serde::{Deserialize, Deserializer, de::{DeserializeSeed, Visitor, SeqAccess, MapAccess, Error as DeError}};
+use crate::prelude::{CommandQueue, Event, Context, EMPTY};
+use super::{MemBuffer, MemFlag, DeviceElement, DEFAULT_CHUNK_SIZE};
+
+/// Largest device allocation made before any element has been read, in bytes
+const MAX_UPFRONT_SIZE: usize = 16 * DEFAULT_CHUNK_SIZE;
+
+/// Deserializes a [```MemBuffer```] into an explicit context, with explicit flags.
+///
+/// Being a [```DeserializeSeed```], it can be nested inside the (seeded) deserialization of larger structs, like model checkpoints.
+/// Use [```vec```](BufferSeed::vec) and [```map```](BufferSeed::map) to deserialize collections of buffers.
+///
+/// When the format knows the sequence's length in advance (i.e. ```bincode```), elements are written straight into the new buffer through ```queue```, in chunks of up to [```DEFAULT_CHUNK_SIZE```] bytes.
+/// Otherwise, they're collected on the host first.
+///
+/// The announced length isn't trusted for the initial allocation: buffers over 16 chunks are allocated with that size, and grow on the device as elements arrive.
+pub struct BufferSeed<'a, T> {
+    ctx: &'a Context,
+    queue: &'a CommandQueue,
+    flags: MemFlag,
+    phtm: PhantomData<T>
+}
+
+impl<'a, T: DeviceElement> BufferSeed<'a, T> {
+    /// Creates a new seed. ```queue``` must belong to ```ctx```
+    #[inline(always)]
+    pub fn new (ctx: &'a Context, queue: &'a CommandQueue, flags: MemFlag) -> Self {
+        Self { ctx, queue, flags, phtm: PhantomData }
+    }
+
+    /// Creates a new seed for the default context and command queue
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn with_flags (flags: MemFlag) -> Self {
+        Self::new(Context::default(), CommandQueue::default(), flags)
+    }
+
+    #[inline(always)]
+    pub fn context (&self) -> &'a Context {
+        self.ctx
+    }
+
+    #[inline(always)]
+    pub fn queue (&self) -> &'a CommandQueue {
+        self.queue
+    }
+
+    #[inline(always)]
+    pub fn flags (&self) -> MemFlag {
+        self.flags
+    }
+
+    /// Returns a seed that deserializes a sequence of buffers
+    #[inline(always)]
+    pub fn vec (self) -> BufferVecSeed<'a, T> {
+        BufferVecSeed { inner: self }
+    }
+
+    /// Returns a seed that deserializes a map of buffers into ```M``` (i.e. ```HashMap<String, MemBuffer<T>>```)
+    #[inline(always)]
+    pub fn map<K, M> (self) -> BufferMapSeed<'a, T, K, M> {
+        BufferMapSeed { inner: self, phtm: PhantomData }
+    }
+}
+
+#[cfg(feature = "def")]
+impl<T: DeviceElement> Default for BufferSeed<'_, T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::with_flags(MemFlag::default())
+    }
+}
+
+impl<T> Clone for BufferSeed<'_, T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for BufferSeed<'_, T> {}
+
+impl<'de, T: DeviceElement + Deserialize<'de>> DeserializeSeed<'de> for BufferSeed<'_, T> {
+    type Value = MemBuffer<T>;
+
+    #[inline(always)]
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error> where D: Deserializer<'de> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, T: DeviceElement + Deserialize<'de>> Visitor<'de> for BufferSeed<'_, T> {
+    type Value = MemBuffer<T>;
+
+    #[inline(always)]
+    fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("a sequence of buffer elements")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>> (self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let len = match seq.size_hint() {
+            Some(len) if len > 0 => len,
+            _ => {
+                let vec = Vec::<T>::deserialize(serde::de::value::SeqAccessDeserializer::new(seq))?;
+                return MemBuffer::with_context(self.ctx, self.flags, &vec).map_err(A::Error::custom)
+            }
+        };
+
+        // don't trust the size hint blindly
+        let flags = self.flags - (MemFlag::USE_HOST_PTR | MemFlag::COPY_HOST_PTR);
+        let mut cap = len.min((MAX_UPFRONT_SIZE / core::mem::size_of::<T>()).max(1));
+        let mut buffer = unsafe { MemBuffer::<T>::uninit_with_context(self.ctx, cap, flags).map_err(A::Error::custom)? };
+        let chunk_len = (DEFAULT_CHUNK_SIZE / core::mem::size_of::<T>()).clamp(1, len);
+        let mut chunk = Vec::<T>::with_capacity(chunk_len);
+        let mut offset = 0;
+
+        loop {
+            let next = match offset + chunk.len() {
+                i if i < len => seq.next_element::<T>()?,
+                // the format promised `len` elements, so there can't be any more
+                _ => match seq.next_element::<serde::de::IgnoredAny>()? {
+                    Some(_) => return Err(A::Error::invalid_length(len + 1, &self)),
+                    None => None
+                }
+            };
+
+            if let Some(next) = next {
+                chunk.push(next);
+                if chunk.len() < chunk_len { continue }
+            }
+
+            if !chunk.is_empty() {
+                if offset + chunk.len() > cap {
+                    let new_cap = cap.saturating_mul(2).max(offset + chunk.len()).min(len);
+                    let mut new = unsafe { MemBuffer::<T>::uninit_with_context(self.ctx, new_cap, flags).map_err(A::Error::custom)? };
+                    buffer.copy_to_with_queue(self.queue, 0, &mut new, 0..offset, EMPTY)
+                        .and_then(|evt| evt.wait())
+                        .map_err(A::Error::custom)?;
+
+                    buffer = new;
+                    cap = new_cap;
+                }
+
+                buffer.write_with_queue(self.queue, offset, &chunk, EMPTY)
+                    .and_then(|evt| evt.wait())
+                    .map_err(A::Error::custom)?;
+
+                offset += chunk.len();
+                chunk.clear();
+            }
+
+            if next.is_none() { break }
+        }
+
+        if offset != len {
+            return Err(A::Error::invalid_length(offset, &self))
+        }
+
+        Ok(buffer)
+    }
+}
+
+/// Deserializes a sequence of [```MemBuffer```]s. Created with [```BufferSeed::vec```]
+pub struct BufferVecSeed<'a, T> {
+    inner: BufferSeed<'a, T>
+}
+
+impl<'de, T: DeviceElement + Deserialize<'de>> DeserializeSeed<'de> for BufferVecSeed<'_, T> {
+    type Value = Vec<MemBuffer<T>>;
+
+    #[inline(always)]
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error> where D: Deserializer<'de> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, T: DeviceElement + Deserialize<'de>> Visitor<'de> for BufferVecSeed<'_, T> {
+    type Value = Vec<MemBuffer<T>>;
+
+    #[inline(always)]
+    fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("a sequence of buffers")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>> (self, mut seq: A) -> Result<Self::Value, A::Error> {
+        // don't trust the size hint blindly
+        let mut result = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(buffer) = seq.next_element_seed(self.inner)? {
+            result.push(buffer);
+        }
+
+        Ok(result)
+    }
+}
+
+/// Deserializes a map of [```MemBuffer```]s into ```M```. Created with [```BufferSeed::map```]
+pub struct BufferMapSeed<'a, T, K, M> {
+    inner: BufferSeed<'a, T>,
+    phtm: PhantomData<(K, M)>
+}
+
+impl<'de, T: DeviceElement + Deserialize<'de>, K: Deserialize<'de>, M: Default + Extend<(K, MemBuffer<T>)>> DeserializeSeed<'de> for BufferMapSeed<'_, T, K, M> {
+    type Value = M;
+
+    #[inline(always)]
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error> where D: Deserializer<'de> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, T: DeviceElement + Deserialize<'de>, K: Deserialize<'de>, M: Default + Extend<(K, MemBuffer<T>)>> Visitor<'de> for BufferMapSeed<'_, T, K, M> {
+    type Value = M;
+
+    #[inline(always)]
+    fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("a map of buffers")
+    }
+
+    fn visit_map<A: MapAccess<'de>> (self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut result = M::default();
+        while let Some(key) = map.next_key::<K>()? {
+            let buffer = map.next_value_seed(self.inner)?;
+            result.extend(core::iter::once((key, buffer)));
+        }
+
+        Ok(result)
+    }
+}
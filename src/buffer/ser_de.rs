@@ -1,9 +1,9 @@
 use alloc::vec::Vec;
 use serde::{Serialize, Deserialize};
 use crate::prelude::{CommandQueue, Event, Context, BaseEvent, EMPTY};
-use super::{MemBuffer, MemFlag};
+use super::{MemBuffer, MemFlag, DeviceElement};
 
-impl<T: Copy + Unpin + Serialize> MemBuffer<T> {
+impl<T: DeviceElement + Serialize> MemBuffer<T> {
     #[cfg(feature = "def")]
     #[inline(always)]
     pub fn serialize_with_wait<S> (&self, serializer: S, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<S::Ok, S::Error> where S: serde::Serializer {
@@ -32,14 +32,14 @@ impl<T: Copy + Unpin + Serialize> MemBuffer<T> {
 }
 
 #[cfg(feature = "def")]
-impl<T: Copy + Unpin + Serialize> Serialize for MemBuffer<T> {
+impl<T: DeviceElement + Serialize> Serialize for MemBuffer<T> {
     #[inline(always)]
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
         self.serialize_with_wait(serializer, EMPTY)
     }
 }
 
-impl<'de, T: Copy + Unpin + Deserialize<'de>> MemBuffer<T> {
+impl<'de, T: DeviceElement + Deserialize<'de>> MemBuffer<T> {
     #[inline(always)]
     pub fn deserialize_with_context<D> (ctx: &Context, deserializer: D) -> Result<Self, D::Error> where D: serde::Deserializer<'de> {
         let vec = Vec::<T>::deserialize(deserializer)?;
@@ -48,7 +48,7 @@ impl<'de, T: Copy + Unpin + Deserialize<'de>> MemBuffer<T> {
 }
 
 #[cfg(feature = "def")]
-impl<'de, T: Copy + Unpin + Deserialize<'de>> Deserialize<'de> for MemBuffer<T> {
+impl<'de, T: DeviceElement + Deserialize<'de>> Deserialize<'de> for MemBuffer<T> {
     #[inline(always)]
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::Deserializer<'de> {
         Self::deserialize_with_context(Context::default(), deserializer)
@@ -0,0 +1,149 @@
+//! Compact binary representation for [```MemBuffer```], to be used with ```#[serde(with = "hlocl::buffer::serde_bytes")]```.
+//!
+//! Buffers are serialized as a single byte blob, made of a 13 byte header followed by the buffer's raw contents.
+//! The header contains the endianness of the host that serialized the buffer (```0``` for little endian, ```1``` for big endian),
+//! the element size as a little endian ```u32``` and the buffer's length as a little endian ```u64```.
+//! All three are checked against ```T``` and the current host when deserializing.
+//! ```ignore
+//! use hlocl::prelude::*;
+//! use serde::{Serialize, Deserialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Weights {
+//!     #[serde(with = "hlocl::buffer::serde_bytes")]
+//!     values: MemBuffer<f32>
+//! }
+//! ```
+
+use core::{marker::PhantomData, ptr::NonNull};
+use alloc::vec::Vec;
+use serde::{Serializer, Deserializer, de::{Visitor, SeqAccess, Error as DeError, Unexpected}, ser::Error as SerError};
+use crate::prelude::{CommandQueue, Context, BaseEvent, Event};
+use super::{MemBuffer, MemFlag, DeviceElement};
+
+/// Size, in bytes, of the blob's header
+pub const HEADER_LEN: usize = 13;
+
+const LITTLE_ENDIAN: u8 = 0;
+const BIG_ENDIAN: u8 = 1;
+const NATIVE_ENDIAN: u8 = if cfg!(target_endian = "little") { LITTLE_ENDIAN } else { BIG_ENDIAN };
+
+/// Serializes ```buffer``` as a byte blob, with the default command queue
+#[cfg(feature = "def")]
+#[inline(always)]
+pub fn serialize<T: DeviceElement, S: Serializer> (buffer: &MemBuffer<T>, serializer: S) -> Result<S::Ok, S::Error> {
+    serialize_with_queue(CommandQueue::default(), buffer, serializer, crate::prelude::EMPTY)
+}
+
+/// Serializes ```buffer``` as a byte blob, reading its contents with ```queue```
+pub fn serialize_with_queue<T: DeviceElement, S: Serializer> (queue: &CommandQueue, buffer: &MemBuffer<T>, serializer: S, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<S::Ok, S::Error> {
+    let len = buffer.len().map_err(S::Error::custom)?;
+    let size = buffer.byte_size().map_err(S::Error::custom)?;
+
+    let mut blob = Vec::<u8>::with_capacity(HEADER_LEN + size);
+    blob.push(NATIVE_ENDIAN);
+    blob.extend_from_slice(&(core::mem::size_of::<T>() as u32).to_le_bytes());
+    blob.extend_from_slice(&(len as u64).to_le_bytes());
+    blob.resize(HEADER_LEN + size, 0);
+
+    buffer.as_bytes()
+        .read_into_with_queue(queue, 0, &mut blob[HEADER_LEN..], wait)
+        .and_then(|evt| evt.wait())
+        .map_err(S::Error::custom)?;
+
+    serializer.serialize_bytes(&blob)
+}
+
+/// Deserializes a byte blob into a new buffer, created on the default context
+#[cfg(feature = "def")]
+#[inline(always)]
+pub fn deserialize<'de, T: DeviceElement, D: Deserializer<'de>> (deserializer: D) -> Result<MemBuffer<T>, D::Error> {
+    deserialize_with_context(Context::default(), MemFlag::default(), deserializer)
+}
+
+/// Deserializes a byte blob into a new buffer, created on ```ctx```.
+///
+/// For formats that support byte blobs natively, the blob is copied straight into the new buffer.
+pub fn deserialize_with_context<'de, T: DeviceElement, D: Deserializer<'de>> (ctx: &Context, flags: MemFlag, deserializer: D) -> Result<MemBuffer<T>, D::Error> {
+    deserializer.deserialize_bytes(BlobVisitor { ctx, flags, phtm: PhantomData })
+}
+
+struct BlobVisitor<'a, T> {
+    ctx: &'a Context,
+    flags: MemFlag,
+    phtm: PhantomData<T>
+}
+
+impl<T: DeviceElement> BlobVisitor<'_, T> {
+    /// Checks the header, returning the buffer's length
+    fn check_header<E: DeError> (header: &[u8; HEADER_LEN]) -> Result<usize, E> {
+        if header[0] > BIG_ENDIAN {
+            return Err(E::invalid_value(Unexpected::Unsigned(header[0] as u64), &"an endianness tag of 0 or 1"))
+        } else if header[0] != NATIVE_ENDIAN {
+            return Err(E::custom("buffer was serialized on a host with a different endianness"))
+        }
+
+        let size = u32::from_le_bytes(header[1..5].try_into().unwrap()) as usize;
+        if size != core::mem::size_of::<T>() {
+            return Err(E::custom(format_args!("expected an element size of {} bytes, found {size}", core::mem::size_of::<T>())))
+        }
+
+        let len = u64::from_le_bytes(header[5..].try_into().unwrap());
+        usize::try_from(len).ok()
+            .filter(|len| len.checked_mul(size).is_some())
+            .ok_or_else(|| E::custom(format_args!("buffer length {len} is too large for this host")))
+    }
+
+    fn build<E: DeError> (&self, len: usize, data: &[u8]) -> Result<MemBuffer<T>, E> {
+        let size = len * core::mem::size_of::<T>();
+        if data.len() != size {
+            return Err(E::invalid_length(HEADER_LEN + data.len(), self))
+        }
+
+        // OpenCL only reads from the host pointer when copying it, so it doesn't need to be mutable or aligned to `T`
+        unsafe {
+            MemBuffer::with_host_ptr(self.ctx, len, self.flags | MemFlag::COPY_HOST_PTR, NonNull::new(data.as_ptr() as *mut T))
+                .map_err(E::custom)
+        }
+    }
+}
+
+impl<'de, T: DeviceElement> Visitor<'de> for BlobVisitor<'_, T> {
+    type Value = MemBuffer<T>;
+
+    #[inline(always)]
+    fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "a byte blob with a {HEADER_LEN} byte header")
+    }
+
+    fn visit_bytes<E: DeError> (self, v: &[u8]) -> Result<Self::Value, E> {
+        let header = v.get(..HEADER_LEN)
+            .and_then(|x| <&[u8; HEADER_LEN]>::try_from(x).ok())
+            .ok_or_else(|| E::invalid_length(v.len(), &self))?;
+
+        let len = Self::check_header(header)?;
+        self.build(len, &v[HEADER_LEN..])
+    }
+
+    fn visit_seq<A: SeqAccess<'de>> (self, mut seq: A) -> Result<Self::Value, A::Error> {
+        // formats without native byte blobs (i.e. JSON) send them as sequences, so they have to be staged on the host
+        let mut header = [0; HEADER_LEN];
+        for (i, byte) in header.iter_mut().enumerate() {
+            *byte = seq.next_element()?.ok_or_else(|| A::Error::invalid_length(i, &self))?;
+        }
+
+        let len = Self::check_header(&header)?;
+        let size = len * core::mem::size_of::<T>();
+        let mut data = Vec::<u8>::with_capacity(size.min(seq.size_hint().unwrap_or(0)));
+        while let Some(byte) = seq.next_element()? {
+            // the header promised `size` bytes, so there can't be any more
+            if data.len() == size {
+                return Err(A::Error::invalid_length(HEADER_LEN + size + 1, &self))
+            }
+
+            data.push(byte);
+        }
+
+        self.build(len, &data)
+    }
+}
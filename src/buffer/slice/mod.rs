@@ -1 +1 @@
-flat_mod!(read);
\ No newline at end of file
+flat_mod!(read, write);
\ No newline at end of file
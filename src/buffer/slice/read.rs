@@ -1,15 +1,49 @@
-use core::marker::PhantomData;
+use core::{marker::PhantomData, ops::Deref};
 use opencl_sys::{cl_mem, clRetainMemObject};
+use crate::buffer::{MemBuffer, DeviceElement};
 
+/// Read-only view into a region of a [```MemBuffer```], backed by an OpenCL sub-buffer.
 #[repr(transparent)]
-pub struct ReadSlice<'a, T: 'static + Copy + Unpin> (pub(crate) cl_mem, PhantomData<&'a T>);
+pub struct ReadSlice<'a, T: DeviceElement> (pub(crate) MemBuffer<T>, PhantomData<&'a MemBuffer<T>>);
 
-impl<'a, T: Copy + Unpin> Clone for ReadSlice<'a, T> {
+impl<'a, T: DeviceElement> ReadSlice<'a, T> {
+    /// # Safety
+    /// ```id``` must be a valid (already retained) sub-buffer whose parent outlives ```'a```
+    #[inline(always)]
+    pub(crate) unsafe fn from_id (id: cl_mem) -> Self {
+        Self(MemBuffer(id, parking_lot::lock_api::RawRwLock::INIT, PhantomData), PhantomData)
+    }
+}
+
+impl<'a, T: DeviceElement> Deref for ReadSlice<'a, T> {
+    type Target = MemBuffer<T>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a, T: DeviceElement> AsRef<MemBuffer<T>> for ReadSlice<'a, T> {
+    #[inline(always)]
+    fn as_ref(&self) -> &MemBuffer<T> {
+        &self.0
+    }
+}
+
+impl<'a, T: DeviceElement> Clone for ReadSlice<'a, T> {
     fn clone(&self) -> Self {
         unsafe {
-            tri_panic!(clRetainMemObject(self.0))
+            tri_panic!(clRetainMemObject(self.0.0));
+            Self::from_id(self.0.0)
         }
+    }
+}
 
-        Self(self.0.clone(), PhantomData)
+#[cfg(feature = "def")]
+impl<'a, T: DeviceElement + core::fmt::Debug> core::fmt::Debug for ReadSlice<'a, T> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.0, f)
     }
 }
\ No newline at end of file
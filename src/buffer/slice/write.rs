@@ -0,0 +1,161 @@
+use core::{marker::PhantomData, ops::{Deref, RangeBounds}};
+use opencl_sys::{cl_mem};
+use crate::{prelude::{Result, CommandQueue, BaseEvent}, event::{WriteBuffer, FillBuffer, MapBufferMut, WriteBufferRect, various::Then}};
+use crate::buffer::{MemBuffer, DeviceElement, RectLayout, BufferWriter};
+
+/// Mutable view into a region of a [```MemBuffer```], backed by an OpenCL sub-buffer.
+///
+/// The view derefs into a shared [```MemBuffer```], and forwards the buffer's mutating methods. It doesn't hand out ```&mut MemBuffer<T>```,
+/// since the sub-buffer could then be swapped out of the view and outlive the borrow of its parent.
+#[repr(transparent)]
+pub struct WriteSlice<'a, T: DeviceElement> (pub(crate) MemBuffer<T>, PhantomData<&'a mut MemBuffer<T>>);
+
+impl<'a, T: DeviceElement> WriteSlice<'a, T> {
+    /// # Safety
+    /// ```id``` must be a valid (already retained) sub-buffer whose parent is mutably borrowed for ```'a```
+    #[inline(always)]
+    pub(crate) unsafe fn from_id (id: cl_mem) -> Self {
+        Self(MemBuffer(id, parking_lot::lock_api::RawRwLock::INIT, PhantomData), PhantomData)
+    }
+
+    /// Returns a mutable reference to the underlying buffer.
+    /// # Safety
+    /// The buffer must not be moved out of the view (i.e. with [```core::mem::swap```] or [```core::mem::replace```]).
+    #[inline(always)]
+    pub unsafe fn as_mut_unchecked (&mut self) -> &mut MemBuffer<T> {
+        &mut self.0
+    }
+
+    /// See [```MemBuffer::set```]
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn set (&mut self, idx: usize, v: T, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Then<WriteBuffer<'_, '_>, impl FnOnce(&mut ())>> {
+        self.0.set(idx, v, wait)
+    }
+
+    /// See [```MemBuffer::set_with_queue```]
+    #[inline(always)]
+    pub fn set_with_queue (&mut self, queue: &CommandQueue, idx: usize, v: T, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Then<WriteBuffer<'_, '_>, impl FnOnce(&mut ())>> {
+        self.0.set_with_queue(queue, idx, v, wait)
+    }
+
+    /// See [```MemBuffer::write```]
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn write<'b> (&mut self, offset: usize, src: &'b [T], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<WriteBuffer<'b, '_>> {
+        self.0.write(offset, src, wait)
+    }
+
+    /// See [```MemBuffer::write_with_queue```]
+    #[inline(always)]
+    pub fn write_with_queue<'b> (&mut self, queue: &CommandQueue, offset: usize, src: &'b [T], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<WriteBuffer<'b, '_>> {
+        self.0.write_with_queue(queue, offset, src, wait)
+    }
+
+    /// See [```MemBuffer::write_rect```]
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn write_rect<'b> (&mut self, layout: RectLayout, src: &'b [T], src_layout: RectLayout, region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<WriteBufferRect<'b, '_>> {
+        self.0.write_rect(layout, src, src_layout, region, wait)
+    }
+
+    /// See [```MemBuffer::write_rect_with_queue```]
+    #[inline(always)]
+    pub fn write_rect_with_queue<'b> (&mut self, queue: &CommandQueue, layout: RectLayout, src: &'b [T], src_layout: RectLayout, region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<WriteBufferRect<'b, '_>> {
+        self.0.write_rect_with_queue(queue, layout, src, src_layout, region, wait)
+    }
+
+    /// See [```MemBuffer::fill```]
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn fill (&mut self, v: T, range: impl RangeBounds<usize>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<FillBuffer<'_>> {
+        self.0.fill(v, range, wait)
+    }
+
+    /// See [```MemBuffer::fill_with_queue```]
+    #[inline(always)]
+    pub fn fill_with_queue (&mut self, queue: &CommandQueue, v: T, range: impl RangeBounds<usize>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<FillBuffer<'_>> {
+        self.0.fill_with_queue(queue, v, range, wait)
+    }
+
+    /// See [```MemBuffer::fill_pattern```]
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn fill_pattern (&mut self, pattern: &[T], range: impl RangeBounds<usize>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<FillBuffer<'_>> {
+        self.0.fill_pattern(pattern, range, wait)
+    }
+
+    /// See [```MemBuffer::fill_pattern_with_queue```]
+    #[inline(always)]
+    pub fn fill_pattern_with_queue (&mut self, queue: &CommandQueue, pattern: &[T], range: impl RangeBounds<usize>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<FillBuffer<'_>> {
+        self.0.fill_pattern_with_queue(queue, pattern, range, wait)
+    }
+
+    /// See [```MemBuffer::map_mut```]
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn map_mut (&mut self, range: impl RangeBounds<usize>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<MapBufferMut<'_, T>> {
+        self.0.map_mut(range, wait)
+    }
+
+    /// See [```MemBuffer::map_mut_with_queue```]
+    #[inline(always)]
+    pub fn map_mut_with_queue (&mut self, queue: &CommandQueue, range: impl RangeBounds<usize>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<MapBufferMut<'_, T>> {
+        self.0.map_mut_with_queue(queue, range, wait)
+    }
+
+    /// See [```MemBuffer::slice_mut```]
+    #[inline(always)]
+    pub fn slice_mut (&mut self, range: impl RangeBounds<usize>) -> Result<WriteSlice<'_, T>> {
+        self.0.slice_mut(range)
+    }
+
+    /// See [```MemBuffer::cast_mut```]
+    #[inline(always)]
+    pub fn cast_mut<U: DeviceElement> (&mut self) -> Result<WriteSlice<'_, U>> {
+        self.0.cast_mut()
+    }
+
+    /// See [```MemBuffer::as_bytes_mut```]
+    #[inline(always)]
+    pub fn as_bytes_mut (&mut self) -> WriteSlice<'_, u8> {
+        self.0.as_bytes_mut()
+    }
+
+    /// See [```MemBuffer::writer```]
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn writer (&mut self, chunk_size: usize) -> BufferWriter<'_> {
+        self.0.writer(chunk_size)
+    }
+
+    /// See [```MemBuffer::writer_with_queue```]
+    #[inline(always)]
+    pub fn writer_with_queue<'b> (&'b mut self, queue: &'b CommandQueue, chunk_size: usize) -> BufferWriter<'b> {
+        self.0.writer_with_queue(queue, chunk_size)
+    }
+}
+
+impl<'a, T: DeviceElement> Deref for WriteSlice<'a, T> {
+    type Target = MemBuffer<T>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a, T: DeviceElement> AsRef<MemBuffer<T>> for WriteSlice<'a, T> {
+    #[inline(always)]
+    fn as_ref(&self) -> &MemBuffer<T> {
+        &self.0
+    }
+}
+
+#[cfg(feature = "def")]
+impl<'a, T: DeviceElement + core::fmt::Debug> core::fmt::Debug for WriteSlice<'a, T> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.0, f)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,192 @@
+use alloc::{string::String, format};
+use crate::{prelude::*, kernel::Kernel, buffer::{MemFlag, ClType, DeviceElement, ScanOp}};
+use super::codegen::{ProgramCache, pow2_local_size};
+
+lazy_static! {
+    static ref PROGRAMS : ProgramCache = ProgramCache::new();
+}
+
+/// Element types that can be radix sorted on the device
+/// # Safety
+/// [```KEY```](RadixKey::KEY) must map ```x``` (of type ```T```) to an unsigned integer of type ```U``` that preserves the ordering of ```Self```
+pub unsafe trait RadixKey: ClType {
+    /// OpenCL C unsigned integer type with the same size as ```Self```
+    const UNSIGNED: &'static str;
+    /// OpenCL C expression that maps ```x``` into an unsigned integer with the same ordering
+    const KEY: &'static str;
+}
+
+unsafe impl RadixKey for u32 {
+    const UNSIGNED: &'static str = "uint";
+    const KEY: &'static str = "x";
+}
+
+unsafe impl RadixKey for i32 {
+    const UNSIGNED: &'static str = "uint";
+    const KEY: &'static str = "as_uint(x) ^ 0x80000000u";
+}
+
+// negative floats have all their bits flipped, and positive ones only their sign
+unsafe impl RadixKey for f32 {
+    const UNSIGNED: &'static str = "uint";
+    const KEY: &'static str = "as_uint(x) ^ ((uint)(-(int)(as_uint(x) >> 31)) | 0x80000000u)";
+}
+
+unsafe impl RadixKey for u64 {
+    const UNSIGNED: &'static str = "ulong";
+    const KEY: &'static str = "x";
+}
+
+unsafe impl RadixKey for i64 {
+    const UNSIGNED: &'static str = "ulong";
+    const KEY: &'static str = "as_ulong(x) ^ 0x8000000000000000ul";
+}
+
+unsafe impl RadixKey for f64 {
+    const UNSIGNED: &'static str = "ulong";
+    const KEY: &'static str = "as_ulong(x) ^ ((ulong)(-(long)(as_ulong(x) >> 63)) | 0x8000000000000000ul)";
+}
+
+/// Order of a sort
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending
+}
+
+const RADIX_BITS: usize = 4;
+const RADIX: usize = 1 << RADIX_BITS;
+
+impl<T: RadixKey> MemBuffer<T> {
+    /// Sorts the buffer in place with a stable LSD radix sort.
+    /// Floats are ordered by their sign and magnitude, so ```-0.0``` comes before ```0.0```, and NaNs are placed at the ends, depending on their sign.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn sort (&mut self, order: SortOrder, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        self.sort_with_queue(CommandQueue::default(), order, wait)
+    }
+
+    /// Sorts the buffer in place with a stable LSD radix sort.
+    /// Floats are ordered by their sign and magnitude, so ```-0.0``` comes before ```0.0```, and NaNs are placed at the ends, depending on their sign.
+    /// # Errors
+    /// Returns [```Error::InvalidBufferSize```] if the buffer has more than ```u32::MAX``` elements
+    #[inline(always)]
+    pub fn sort_with_queue (&mut self, queue: &CommandQueue, order: SortOrder, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        radix_sort::<T, u8>(queue, self, None, order, wait)
+    }
+
+    /// Sorts ```keys``` in place with a stable LSD radix sort, applying the same permutation to ```values```.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn sort_by_key<V: DeviceElement> (keys: &mut Self, values: &mut MemBuffer<V>, order: SortOrder, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        Self::sort_by_key_with_queue(CommandQueue::default(), keys, values, order, wait)
+    }
+
+    /// Sorts ```keys``` in place with a stable LSD radix sort, applying the same permutation to ```values```.
+    /// # Errors
+    /// Returns [```Error::InvalidBufferSize```] if ```keys``` and ```values``` have different lengths, or if there are more than ```u32::MAX``` keys
+    #[inline(always)]
+    pub fn sort_by_key_with_queue<V: DeviceElement> (queue: &CommandQueue, keys: &mut Self, values: &mut MemBuffer<V>, order: SortOrder, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        let (len, values_len) = (keys.len()?, values.len()?);
+        if len != values_len {
+            #[cfg(feature = "error-stack")]
+            return Err(error_stack::Report::new(Error::InvalidBufferSize).attach_printable(format!("there are {len} keys, but {values_len} values")));
+            #[cfg(not(feature = "error-stack"))]
+            return Err(Error::InvalidBufferSize);
+        }
+
+        radix_sort(queue, keys, Some(values), order, wait)
+    }
+}
+
+/// Sorts ```keys``` (and ```values```, moved as raw bytes) by ping-ponging between them and temporary buffers.
+/// Every key has an even number of digits, so the result always ends up in the original buffers.
+fn radix_sort<T: RadixKey, V: DeviceElement> (queue: &CommandQueue, keys: &mut MemBuffer<T>, values: Option<&mut MemBuffer<V>>, order: SortOrder, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+    let len = keys.len()?;
+    // digit offsets are computed with u32 scans
+    if u32::try_from(len).is_err() {
+        #[cfg(feature = "error-stack")]
+        return Err(error_stack::Report::new(Error::InvalidBufferSize).attach_printable(format!("radix sort supports up to {} elements, but there are {len}", u32::MAX)));
+        #[cfg(not(feature = "error-stack"))]
+        return Err(Error::InvalidBufferSize);
+    }
+
+    let ctx = queue.context()?;
+    let value_size = core::mem::size_of::<V>();
+    let key = match values {
+        Some(_) => format!("{value_size}"),
+        None => String::new()
+    };
+
+    let program = PROGRAMS.get_or_build::<T>(&ctx, &key, || {
+        let mut source = format!("#define U {}\n#define KEY(x) ({})\n", T::UNSIGNED, T::KEY);
+        if values.is_some() {
+            source.push_str(&format!("#define V_SIZE {value_size}\n"));
+        }
+
+        source.push_str(include_str!("sort.ocl"));
+        source
+    })?;
+
+    let mut count = unsafe { Kernel::new_unchecked(&program, "count_digits")? };
+    let mut scatter = unsafe { Kernel::new_unchecked(&program, if values.is_some() { "scatter_pairs" } else { "scatter_keys" })? };
+
+    let wgs = pow2_local_size::<u32>(queue, 1)?;
+    let groups = len.div_ceil(wgs);
+    let flip = match order {
+        SortOrder::Ascending => 0u64,
+        SortOrder::Descending => u64::MAX
+    };
+
+    let tmp_keys = unsafe { MemBuffer::<T>::uninit_with_context(&ctx, len, MemFlag::READ_WRITE)? };
+    let tmp_values = match values {
+        Some(_) => Some(unsafe { MemBuffer::<V>::uninit_with_context(&ctx, len, MemFlag::READ_WRITE)? }),
+        None => None
+    };
+
+    let hist = unsafe { MemBuffer::<u32>::uninit_with_context(&ctx, RADIX * groups, MemFlag::READ_WRITE)? };
+    let passes = 8 * core::mem::size_of::<T>() / RADIX_BITS;
+    let mut evt = queue.marker(wait)?;
+
+    for pass in 0..passes {
+        let (src, dst) = match pass % 2 {
+            0 => (&*keys, &tmp_keys),
+            _ => (&tmp_keys, &*keys)
+        };
+
+        let shift = (pass * RADIX_BITS) as u32;
+        count.set_arg(0, len as u64)?;
+        count.set_mem_arg(1, src)?;
+        count.set_mem_arg(2, &hist)?;
+        count.set_arg(3, shift)?;
+        count.set_arg(4, flip)?;
+        evt = count.enqueue_with_queue(queue, &[groups * wgs], Some(&[wgs]), [evt])?;
+
+        // OpenCL keeps `offsets` alive until the scatter that uses it completes
+        let (offsets, scan) = hist.exclusive_scan_inner(queue, ScanOp::Add, 0, [evt])?;
+
+        scatter.set_arg(0, len as u64)?;
+        scatter.set_mem_arg(1, src)?;
+        scatter.set_mem_arg(2, dst)?;
+        let mut idx = 3;
+        if let (Some(values), Some(tmp_values)) = (values.as_deref(), tmp_values.as_ref()) {
+            let (values_src, values_dst) = match pass % 2 {
+                0 => (values, tmp_values),
+                _ => (tmp_values, values)
+            };
+
+            scatter.set_mem_arg(3, values_src)?;
+            scatter.set_mem_arg(4, values_dst)?;
+            idx = 5;
+        }
+
+        scatter.set_mem_arg(idx, &offsets)?;
+        scatter.set_arg(idx + 1, shift)?;
+        scatter.set_arg(idx + 2, flip)?;
+        scatter.alloc_arg::<u32>(idx + 3, wgs)?;
+        evt = scatter.enqueue_with_queue(queue, &[groups * wgs], Some(&[wgs]), [scan])?;
+    }
+
+    Ok(evt)
+}
@@ -1,8 +1,9 @@
 use core::mem::MaybeUninit;
 use alloc::vec::{Vec};
-use opencl_sys::{cl_context, cl_context_properties, CL_CONTEXT_PLATFORM, CL_CONTEXT_INTEROP_USER_SYNC, clCreateContext, clReleaseContext, clRetainContext, cl_context_info, clGetContextInfo, CL_CONTEXT_REFERENCE_COUNT, CL_CONTEXT_NUM_DEVICES, CL_CONTEXT_DEVICES};
+use opencl_sys::{cl_context, cl_context_properties, CL_CONTEXT_PLATFORM, CL_CONTEXT_INTEROP_USER_SYNC, clCreateContext, clReleaseContext, clRetainContext, cl_context_info, clGetContextInfo, CL_CONTEXT_REFERENCE_COUNT, CL_CONTEXT_NUM_DEVICES, CL_CONTEXT_DEVICES, cl_image_format, clGetSupportedImageFormats};
 use crate::error::Error;
 use crate::prelude::{Platform, Device, Result};
+use crate::{buffer::{MemFlag, MemObjectType}, image::ImageFormat};
 
 #[cfg(feature = "error-stack")]
 use alloc::format;
@@ -79,6 +80,20 @@ impl Context {
         Ok(result)
     }
 
+    /// Returns the image formats supported by the context for images of type ```ty``` created with ```flags```. Formats not known to this crate are skipped.
+    pub fn supported_image_formats (&self, flags: MemFlag, ty: MemObjectType) -> Result<Vec<ImageFormat>> {
+        let mut count = 0;
+        let err = unsafe { clGetSupportedImageFormats(self.0, flags.bits(), ty as u32, 0, core::ptr::null_mut(), &mut count) };
+        Self::parse_image_formats_error(err, flags, ty)?;
+
+        let mut result = Vec::<cl_image_format>::with_capacity(count as usize);
+        let err = unsafe { clGetSupportedImageFormats(self.0, flags.bits(), ty as u32, count, result.as_mut_ptr(), core::ptr::null_mut()) };
+        Self::parse_image_formats_error(err, flags, ty)?;
+        unsafe { result.set_len(count as usize) }
+
+        Ok(result.into_iter().filter_map(ImageFormat::from_raw).collect())
+    }
+
     #[inline]
     pub fn properties (&self) -> Result<ContextProps> {
         todo!()
@@ -101,6 +116,32 @@ impl Context {
         }
     }
 
+    #[allow(unused_variables)]
+    fn parse_image_formats_error (err: i32, flags: MemFlag, ty: MemObjectType) -> Result<()> {
+        if err == 0 {
+            return Ok(());
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "error-stack")] {
+                let err = Error::from(err);
+                let report = error_stack::Report::new(err);
+
+                let report = match err {
+                    Error::InvalidContext => report.attach_printable("context is not a valid context"),
+                    Error::InvalidValue => report.attach_printable(format!("'{flags:?}' or '{ty:?}' are not valid values")),
+                    Error::OutOfResources => report.attach_printable("failure to allocate resources required by the OpenCL implementation on the device"),
+                    Error::OutOfHostMemory => report.attach_printable("failure to allocate resources required by the OpenCL implementation on the host"),
+                    _ => report
+                };
+
+                Err(report)
+            } else {
+                Err(Error::from(err))
+            }
+        }
+    }
+
     #[allow(unused_variables)]
     fn parse_error (&self, err: i32, ty: cl_context_info, size: usize) -> Result<()> {
         if err == 0 {
@@ -1,7 +1,7 @@
-use core::{marker::PhantomData};
+use core::{marker::PhantomData, ptr::NonNull};
 use alloc::{vec::Vec};
-use opencl_sys::{cl_event, clEnqueueWriteBuffer, clEnqueueCopyBuffer, clEnqueueReadBuffer};
-use crate::{prelude::{Result, Error, CommandQueue}, buffer::{MemBuffer}};
+use opencl_sys::{cl_event, cl_map_flags, clEnqueueWriteBuffer, clEnqueueCopyBuffer, clEnqueueReadBuffer, clEnqueueMapBuffer, clEnqueueFillBuffer, CL_MAP_READ, CL_MAP_WRITE, CL_FALSE};
+use crate::{prelude::{Result, Error, CommandQueue}, buffer::{MemBuffer, DeviceElement, MapGuard, MapMutGuard}};
 use super::{BaseEvent, Event};
 
 #[cfg(feature = "error-stack")]
@@ -15,7 +15,21 @@ pub struct CopyBuffer<'a, 'b> {
 }
 
 impl<'a, 'b> CopyBuffer<'a, 'b> {
-    pub fn new<T: 'static + Copy + Unpin> (queue: &CommandQueue, src_offset: usize, dst_offset: usize, len: usize, src: &'a MemBuffer<T>, dst: &'b mut MemBuffer<T>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Self> {
+    /// Copies ```len``` elements from ```src```, starting at ```src_offset```, into ```dst```, starting at ```dst_offset```.
+    #[inline(always)]
+    pub fn new<T: DeviceElement> (queue: &CommandQueue, src_offset: usize, dst_offset: usize, len: usize, src: &'a MemBuffer<T>, dst: &'b mut MemBuffer<T>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Self> {
+        // SAFETY: Borrow of dst is mutable, so it's safe to write in it
+        unsafe { Self::new_by_ref(queue, src_offset, dst_offset, len, src, dst, wait) }
+    }
+
+    /// Copies ```len``` elements from ```src```, starting at ```src_offset```, into ```dst```, starting at ```dst_offset```.
+    /// # Safety
+    /// No other command may access the destination region of ```dst``` until the copy has completed, and the lifetime ```'b``` isn't checked against ```dst```
+    pub unsafe fn new_by_ref<T: DeviceElement> (queue: &CommandQueue, src_offset: usize, dst_offset: usize, len: usize, src: &'a MemBuffer<T>, dst: &MemBuffer<T>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Self> {
+        let src_offset = src_offset.checked_mul(core::mem::size_of::<T>()).expect("Integer overflow. Too many elements in buffer");
+        let dst_offset = dst_offset.checked_mul(core::mem::size_of::<T>()).expect("Integer overflow. Too many elements in buffer");
+        let len = len.checked_mul(core::mem::size_of::<T>()).expect("Integer overflow. Too many elements in buffer");
+
         let wait = wait.into_iter().map(|x| x.as_ref().0).collect::<Vec<_>>();
         let wait_len = u32::try_from(wait.len()).unwrap();
         let wait = match wait_len {
@@ -24,7 +38,7 @@ impl<'a, 'b> CopyBuffer<'a, 'b> {
         };
 
         let mut event : cl_event = core::ptr::null_mut();
-        let err = unsafe { clEnqueueCopyBuffer(queue.0, src.0, dst.0, src_offset, dst_offset, len, wait_len, wait, &mut event) };
+        let err = clEnqueueCopyBuffer(queue.0, src.0, dst.0, src_offset, dst_offset, len, wait_len, wait, &mut event);
 
         if err == 0 {
             let inner = BaseEvent::new(event)?;
@@ -88,6 +102,94 @@ impl AsRef<BaseEvent> for CopyBuffer<'_, '_> {
     }
 }
 
+/// Event that fills a region of an OpenCL buffer with a repeating pattern
+#[repr(transparent)]
+pub struct FillBuffer<'a> {
+    inner: BaseEvent,
+    phtm: PhantomData<&'a ()>
+}
+
+impl<'a> FillBuffer<'a> {
+    /// Fills ```len``` elements of ```dst```, starting at ```offset```, with copies of ```pattern```. The pattern is copied when the command is enqueued.
+    pub fn new<T: DeviceElement> (queue: &CommandQueue, pattern: &[T], offset: usize, len: usize, dst: &'a mut MemBuffer<T>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Self> {
+        let wait = wait.into_iter().map(|x| x.as_ref().0).collect::<Vec<_>>();
+        let wait_len = u32::try_from(wait.len()).unwrap();
+        let wait = match wait_len {
+            0 => core::ptr::null(),
+            _ => wait.as_ptr()
+        };
+
+        let pattern_size = core::mem::size_of_val(pattern);
+        let offset = offset.checked_mul(core::mem::size_of::<T>()).expect("Integer overflow. Too many elements in buffer");
+        let size = len.checked_mul(core::mem::size_of::<T>()).expect("Integer overflow. Too many elements in buffer");
+
+        let mut event : cl_event = core::ptr::null_mut();
+        let err = unsafe { clEnqueueFillBuffer(queue.0, dst.0, pattern.as_ptr().cast(), pattern_size, offset, size, wait_len, wait, &mut event) };
+
+        if err == 0 {
+            let inner = BaseEvent::new(event)?;
+            return Ok(Self { inner, phtm: PhantomData });
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "error-stack")] {
+                let err = Error::from(err);
+                let report = error_stack::Report::new(err);
+
+                let report = match err {
+                    Error::InvalidCommandQueue => report.attach_printable(format!("'{:?}' is not a valid command-queue", queue.0)),
+                    Error::InvalidContext => report.attach_printable("the context associated with the command queue and buffer are not the same or the context associated with command queue and events in the event wait list are not the same"),
+                    Error::InvalidMemObject => report.attach_printable(format!("'{:?}' is not a valid buffer", dst.0)),
+                    Error::InvalidValue => report.attach_printable(format!("the region being filled is out of bounds, or the pattern size ({pattern_size} bytes) isn't a power of two no larger than 128 bytes, or the region isn't a multiple of the pattern size")),
+                    Error::InvalidEventWaitList => report.attach_printable("event objects in the event wait list are not valid events"),
+                    Error::MisalignedSubBufferOffset => report.attach_printable("buffer is a sub-buffer whose offset is not aligned to the base address alignment of the device"),
+                    Error::MemObjectAllocationFailure => report.attach_printable("there is a failure to allocate memory for data store associated with buffer"),
+                    Error::OutOfResources => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the device"),
+                    Error::OutOfHostMemory => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the host"),
+                    _ => report
+                };
+
+                Err(report)
+            } else {
+                Err(Error::from(err))
+            }
+        }
+    }
+}
+
+impl Event for FillBuffer<'_> {
+    type Result = ();
+
+    #[inline(always)]
+    fn wait (self) -> Result<Self::Result> {
+        self.inner.wait()?;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn wait_all (iter: impl IntoIterator<Item = Self>) -> Result<alloc::vec::Vec<Self::Result>> {
+        let iter = iter.into_iter().map(|x| x.inner);
+        BaseEvent::wait_all(iter)
+    }
+}
+
+#[cfg(feature = "async")]
+impl futures::Future for FillBuffer<'_> {
+    type Output = Result<()>;
+
+    #[inline(always)]
+    fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<Self::Output> {
+        core::pin::Pin::new(&mut self.inner).poll(cx)
+    }
+}
+
+impl AsRef<BaseEvent> for FillBuffer<'_> {
+    #[inline(always)]
+    fn as_ref(&self) -> &BaseEvent {
+        &self.inner
+    }
+}
+
 /// Event that writes from host memory to an OpenCL buffer
 #[repr(transparent)]
 pub struct WriteBuffer<'a, 'b> {
@@ -96,7 +198,7 @@ pub struct WriteBuffer<'a, 'b> {
 }
 
 impl<'a, 'b> WriteBuffer<'a, 'b> {
-    pub unsafe fn new_by_ref<T: 'static + Copy + Unpin> (queue: &CommandQueue, blocking: bool, offset: usize, src: &'a [T], dst: &'b MemBuffer<T>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Self> {
+    pub unsafe fn new_by_ref<T: DeviceElement> (queue: &CommandQueue, blocking: bool, offset: usize, src: &'a [T], dst: &'b MemBuffer<T>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Self> {
         let wait = wait.into_iter().map(|x| x.as_ref().0).collect::<Vec<_>>();
         let wait_len = u32::try_from(wait.len()).unwrap();
         let wait = match wait_len {
@@ -139,10 +241,17 @@ impl<'a, 'b> WriteBuffer<'a, 'b> {
     }
 
     #[inline(always)]
-    pub fn new<T: Copy + Unpin> (queue: &CommandQueue, blocking: bool, offset: usize, src: &'a [T], dst: &'b mut MemBuffer<T>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Self> {
+    pub fn new<T: DeviceElement> (queue: &CommandQueue, blocking: bool, offset: usize, src: &'a [T], dst: &'b mut MemBuffer<T>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Self> {
         // SAFETY: Borrow of dst is mutable, so it's safe to write in it
         unsafe { Self::new_by_ref(queue, blocking, offset, src, dst, wait) }
     }
+
+    /// # Safety
+    /// ```inner``` must be the event of a write from memory borrowed for ```'a``` into a buffer borrowed for ```'b```, or of a command that accesses neither
+    #[inline(always)]
+    pub(crate) unsafe fn from_inner (inner: BaseEvent) -> Self {
+        Self { inner, phtm: PhantomData }
+    }
 }
 
 impl Event for WriteBuffer<'_, '_> {
@@ -184,7 +293,7 @@ pub struct ReadBuffer<'a, 'b> {
 }
 
 impl<'a, 'b> ReadBuffer<'a, 'b> {
-    pub fn new<T: Copy + Unpin> (queue: &CommandQueue, blocking: bool, offset: usize, src: &'a MemBuffer<T>, dst: &'b mut [T], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Self> {
+    pub fn new<T: DeviceElement> (queue: &CommandQueue, blocking: bool, offset: usize, src: &'a MemBuffer<T>, dst: &'b mut [T], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Self> {
         let wait = wait.into_iter().map(|x| x.as_ref().0).collect::<Vec<_>>();
         let wait_len = u32::try_from(wait.len()).unwrap();
         let wait = match wait_len {
@@ -258,4 +367,180 @@ impl AsRef<BaseEvent> for ReadBuffer<'_, '_> {
     fn as_ref(&self) -> &BaseEvent {
         &self.inner
     }
-}
\ No newline at end of file
+}
+
+/// Event that maps a region of an OpenCL buffer into host memory for reading
+pub struct MapBuffer<'a, T: DeviceElement> {
+    inner: BaseEvent,
+    #[cfg(not(feature = "async"))]
+    guard: MapGuard<'a, T>,
+    #[cfg(feature = "async")]
+    guard: Option<MapGuard<'a, T>>
+}
+
+impl<'a, T: DeviceElement> MapBuffer<'a, T> {
+    #[inline]
+    pub fn new (queue: &CommandQueue, offset: usize, len: usize, src: &'a MemBuffer<T>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Self> {
+        let (inner, ptr) = unsafe { enqueue_map(queue, src, CL_MAP_READ, offset, len, wait)? };
+        let guard = unsafe { MapGuard::from_raw_parts(queue.clone(), src.0, ptr, len) };
+        #[cfg(feature = "async")]
+        let guard = Some(guard);
+        Ok(Self { inner, guard })
+    }
+}
+
+impl<'a, T: DeviceElement> Event for MapBuffer<'a, T> {
+    type Result = MapGuard<'a, T>;
+
+    #[inline(always)]
+    fn wait (self) -> Result<Self::Result> {
+        self.inner.wait()?;
+        #[cfg(feature = "async")]
+        return Ok(self.guard.unwrap());
+        #[cfg(not(feature = "async"))]
+        Ok(self.guard)
+    }
+
+    #[inline(always)]
+    fn wait_all (iter: impl IntoIterator<Item = Self>) -> Result<alloc::vec::Vec<Self::Result>> {
+        #[cfg(feature = "async")]
+        let (inner, guards) : (Vec<_>, Vec<_>) = iter.into_iter().map(|x| (x.inner, x.guard.unwrap())).unzip();
+        #[cfg(not(feature = "async"))]
+        let (inner, guards) : (Vec<_>, Vec<_>) = iter.into_iter().map(|x| (x.inner, x.guard)).unzip();
+        BaseEvent::wait_all(inner)?;
+        Ok(guards)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<'a, T: DeviceElement> futures::Future for MapBuffer<'a, T> {
+    type Output = Result<MapGuard<'a, T>>;
+
+    #[inline(always)]
+    fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<Self::Output> {
+        if let core::task::Poll::Ready(_) = core::pin::Pin::new(&mut self.inner).poll(cx)? {
+            return core::task::Poll::Ready(Ok(self.guard.take().unwrap()))
+        }
+
+        core::task::Poll::Pending
+    }
+}
+
+impl<T: DeviceElement> AsRef<BaseEvent> for MapBuffer<'_, T> {
+    #[inline(always)]
+    fn as_ref(&self) -> &BaseEvent {
+        &self.inner
+    }
+}
+
+/// Event that maps a region of an OpenCL buffer into host memory for reading and writing
+pub struct MapBufferMut<'a, T: DeviceElement> {
+    inner: BaseEvent,
+    #[cfg(not(feature = "async"))]
+    guard: MapMutGuard<'a, T>,
+    #[cfg(feature = "async")]
+    guard: Option<MapMutGuard<'a, T>>
+}
+
+impl<'a, T: DeviceElement> MapBufferMut<'a, T> {
+    #[inline]
+    pub fn new (queue: &CommandQueue, offset: usize, len: usize, src: &'a mut MemBuffer<T>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Self> {
+        let (inner, ptr) = unsafe { enqueue_map(queue, src, CL_MAP_READ | CL_MAP_WRITE, offset, len, wait)? };
+        let guard = unsafe { MapMutGuard::from_raw_parts(queue.clone(), src.0, ptr, len) };
+        #[cfg(feature = "async")]
+        let guard = Some(guard);
+        Ok(Self { inner, guard })
+    }
+}
+
+impl<'a, T: DeviceElement> Event for MapBufferMut<'a, T> {
+    type Result = MapMutGuard<'a, T>;
+
+    #[inline(always)]
+    fn wait (self) -> Result<Self::Result> {
+        self.inner.wait()?;
+        #[cfg(feature = "async")]
+        return Ok(self.guard.unwrap());
+        #[cfg(not(feature = "async"))]
+        Ok(self.guard)
+    }
+
+    #[inline(always)]
+    fn wait_all (iter: impl IntoIterator<Item = Self>) -> Result<alloc::vec::Vec<Self::Result>> {
+        #[cfg(feature = "async")]
+        let (inner, guards) : (Vec<_>, Vec<_>) = iter.into_iter().map(|x| (x.inner, x.guard.unwrap())).unzip();
+        #[cfg(not(feature = "async"))]
+        let (inner, guards) : (Vec<_>, Vec<_>) = iter.into_iter().map(|x| (x.inner, x.guard)).unzip();
+        BaseEvent::wait_all(inner)?;
+        Ok(guards)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<'a, T: DeviceElement> futures::Future for MapBufferMut<'a, T> {
+    type Output = Result<MapMutGuard<'a, T>>;
+
+    #[inline(always)]
+    fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<Self::Output> {
+        if let core::task::Poll::Ready(_) = core::pin::Pin::new(&mut self.inner).poll(cx)? {
+            return core::task::Poll::Ready(Ok(self.guard.take().unwrap()))
+        }
+
+        core::task::Poll::Pending
+    }
+}
+
+impl<T: DeviceElement> AsRef<BaseEvent> for MapBufferMut<'_, T> {
+    #[inline(always)]
+    fn as_ref(&self) -> &BaseEvent {
+        &self.inner
+    }
+}
+
+/// # Safety
+/// The mapped region must not be accessed until the returned event completes
+unsafe fn enqueue_map<T: DeviceElement> (queue: &CommandQueue, src: &MemBuffer<T>, flags: cl_map_flags, offset: usize, len: usize, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<(BaseEvent, NonNull<T>)> {
+    let wait = wait.into_iter().map(|x| x.as_ref().0).collect::<Vec<_>>();
+    let wait_len = u32::try_from(wait.len()).unwrap();
+    let wait = match wait_len {
+        0 => core::ptr::null(),
+        _ => wait.as_ptr()
+    };
+
+    let offset = offset.checked_mul(core::mem::size_of::<T>()).expect("Integer overflow. Too many elements in buffer");
+    let size = len.checked_mul(core::mem::size_of::<T>()).expect("Integer overflow. Too many elements in buffer");
+
+    let mut err = 0;
+    let mut event : cl_event = core::ptr::null_mut();
+    let ptr = clEnqueueMapBuffer(queue.0, src.0, CL_FALSE, flags, offset, size, wait_len, wait, &mut event, &mut err);
+
+    if err == 0 {
+        let inner = BaseEvent::new(event)?;
+        return Ok((inner, NonNull::new(ptr.cast()).unwrap()));
+    }
+
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "error-stack")] {
+            let err = Error::from(err);
+            let report = error_stack::Report::new(err);
+
+            let report = match err {
+                Error::InvalidCommandQueue => report.attach_printable(format!("'{:?}' is not a valid command-queue", queue.0)),
+                Error::InvalidContext => report.attach_printable("the context associated with the command queue and buffer are not the same or the context associated with command queue and events in the event wait list are not the same"),
+                Error::InvalidMemObject => report.attach_printable(format!("'{:?}' is not a valid buffer", src.0)),
+                Error::InvalidValue => report.attach_printable("the region being mapped is out of bounds or its size is zero"),
+                Error::InvalidEventWaitList => report.attach_printable("event objects in the event wait list are not valid events"),
+                Error::MisalignedSubBufferOffset => report.attach_printable("buffer is a sub-buffer whose offset is not aligned to the base address alignment of the device"),
+                Error::MapFailure => report.attach_printable("there is a failure to map the requested region into the host address space"),
+                Error::MemObjectAllocationFailure => report.attach_printable("there is a failure to allocate memory for data store associated with buffer"),
+                Error::OutOfResources => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the device"),
+                Error::OutOfHostMemory => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the host"),
+                _ => report
+            };
+
+            Err(report)
+        } else {
+            Err(Error::from(err))
+        }
+    }
+}
@@ -0,0 +1,542 @@
+use core::marker::PhantomData;
+use alloc::vec::Vec;
+use opencl_sys::{cl_event, clEnqueueReadImage, clEnqueueWriteImage, clEnqueueCopyImage, clEnqueueCopyImageToBuffer, clEnqueueCopyBufferToImage, clEnqueueFillImage};
+use crate::{prelude::{Result, Error, CommandQueue}, buffer::MemBuffer, image::{Image, ImagePixel, ImageDim}};
+use super::{BaseEvent, Event};
+
+#[cfg(feature = "error-stack")]
+use alloc::format;
+
+/// Event that reads a region of an OpenCL image to host memory
+#[repr(transparent)]
+pub struct ReadImage<'a, 'b> {
+    inner: BaseEvent,
+    phtm: PhantomData<(&'a (), &'b ())>
+}
+
+impl<'a, 'b> ReadImage<'a, 'b> {
+    /// Reads the ```region``` at ```origin``` into ```dst```, tightly packed.
+    /// # Errors
+    /// Returns [```Error::InvalidValue```] if ```dst``` is too small to hold the region.
+    pub fn new<T: ImagePixel, D: ImageDim> (queue: &CommandQueue, blocking: bool, src: &'a Image<T, D>, origin: [usize; 3], region: [usize; 3], dst: &'b mut [T], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Self> {
+        check_host(region, dst.len())?;
+        let wait = wait.into_iter().map(|x| x.as_ref().0).collect::<Vec<_>>();
+        let wait_len = u32::try_from(wait.len()).unwrap();
+        let wait = match wait_len {
+            0 => core::ptr::null(),
+            _ => wait.as_ptr()
+        };
+
+        let mut event : cl_event = core::ptr::null_mut();
+        let err = unsafe { clEnqueueReadImage(queue.0, src.0, opencl_sys::cl_bool::from(blocking), origin.as_ptr(), region.as_ptr(), 0, 0, dst.as_mut_ptr().cast(), wait_len, wait, &mut event) };
+
+        if err == 0 {
+            let inner = BaseEvent::new(event)?;
+            return Ok(Self { inner, phtm: PhantomData });
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "error-stack")] {
+                let err = Error::from(err);
+                let report = error_stack::Report::new(err);
+
+                let report = match err {
+                    Error::InvalidCommandQueue => report.attach_printable(format!("'{:?}' is not a valid command-queue", queue.0)),
+                    Error::InvalidContext => report.attach_printable("the context associated with the command queue and memory objects are not the same or the context associated with command queue and events in the event wait list are not the same"),
+                    Error::InvalidMemObject => report.attach_printable(format!("'{:?}' is not a valid image", src.0)),
+                    Error::InvalidValue => report.attach_printable("the region being read is out of bounds or the origin and region are not valid for the image type"),
+                    Error::InvalidEventWaitList => report.attach_printable("event objects in the event wait list are not valid events"),
+                    Error::InvalidImageSize => report.attach_printable("image dimensions are not supported by the device associated with the command queue"),
+                    Error::ImageFormatNotSupported => report.attach_printable("image format is not supported by the device associated with the command queue"),
+                    Error::InvalidOperation => report.attach_printable("the device associated with the command queue does not support images"),
+                    Error::MemObjectAllocationFailure => report.attach_printable("there is a failure to allocate memory for data store associated with the image"),
+                    Error::OutOfResources => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the device"),
+                    Error::OutOfHostMemory => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the host"),
+                    _ => report
+                };
+
+                Err(report)
+            } else {
+                Err(Error::from(err))
+            }
+        }
+    }
+}
+
+impl Event for ReadImage<'_, '_> {
+    type Result = ();
+
+    #[inline(always)]
+    fn wait (self) -> Result<Self::Result> {
+        self.inner.wait()
+    }
+
+    #[inline(always)]
+    fn wait_all (iter: impl IntoIterator<Item = Self>) -> Result<alloc::vec::Vec<Self::Result>> {
+        let iter = iter.into_iter().map(|x| x.inner);
+        BaseEvent::wait_all(iter)
+    }
+}
+
+#[cfg(feature = "async")]
+impl futures::Future for ReadImage<'_, '_> {
+    type Output = Result<()>;
+
+    #[inline(always)]
+    fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<Self::Output> {
+        core::pin::Pin::new(&mut self.inner).poll(cx)
+    }
+}
+
+impl AsRef<BaseEvent> for ReadImage<'_, '_> {
+    #[inline(always)]
+    fn as_ref(&self) -> &BaseEvent {
+        &self.inner
+    }
+}
+
+/// Event that writes from host memory to a region of an OpenCL image
+#[repr(transparent)]
+pub struct WriteImage<'a, 'b> {
+    inner: BaseEvent,
+    phtm: PhantomData<(&'a (), &'b ())>
+}
+
+impl<'a, 'b> WriteImage<'a, 'b> {
+    /// Writes the tightly packed pixels of ```src``` into the ```region``` at ```origin```.
+    /// # Errors
+    /// Returns [```Error::InvalidValue```] if ```src``` is too small to fill the region.
+    pub fn new<T: ImagePixel, D: ImageDim> (queue: &CommandQueue, blocking: bool, src: &'a [T], dst: &'b mut Image<T, D>, origin: [usize; 3], region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Self> {
+        check_host(region, src.len())?;
+        let wait = wait.into_iter().map(|x| x.as_ref().0).collect::<Vec<_>>();
+        let wait_len = u32::try_from(wait.len()).unwrap();
+        let wait = match wait_len {
+            0 => core::ptr::null(),
+            _ => wait.as_ptr()
+        };
+
+        let mut event : cl_event = core::ptr::null_mut();
+        let err = unsafe { clEnqueueWriteImage(queue.0, dst.0, opencl_sys::cl_bool::from(blocking), origin.as_ptr(), region.as_ptr(), 0, 0, src.as_ptr().cast(), wait_len, wait, &mut event) };
+
+        if err == 0 {
+            let inner = BaseEvent::new(event)?;
+            return Ok(Self { inner, phtm: PhantomData });
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "error-stack")] {
+                let err = Error::from(err);
+                let report = error_stack::Report::new(err);
+
+                let report = match err {
+                    Error::InvalidCommandQueue => report.attach_printable(format!("'{:?}' is not a valid command-queue", queue.0)),
+                    Error::InvalidContext => report.attach_printable("the context associated with the command queue and memory objects are not the same or the context associated with command queue and events in the event wait list are not the same"),
+                    Error::InvalidMemObject => report.attach_printable(format!("'{:?}' is not a valid image", dst.0)),
+                    Error::InvalidValue => report.attach_printable("the region being written is out of bounds or the origin and region are not valid for the image type"),
+                    Error::InvalidEventWaitList => report.attach_printable("event objects in the event wait list are not valid events"),
+                    Error::InvalidImageSize => report.attach_printable("image dimensions are not supported by the device associated with the command queue"),
+                    Error::ImageFormatNotSupported => report.attach_printable("image format is not supported by the device associated with the command queue"),
+                    Error::InvalidOperation => report.attach_printable("the device associated with the command queue does not support images"),
+                    Error::MemObjectAllocationFailure => report.attach_printable("there is a failure to allocate memory for data store associated with the image"),
+                    Error::OutOfResources => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the device"),
+                    Error::OutOfHostMemory => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the host"),
+                    _ => report
+                };
+
+                Err(report)
+            } else {
+                Err(Error::from(err))
+            }
+        }
+    }
+}
+
+impl Event for WriteImage<'_, '_> {
+    type Result = ();
+
+    #[inline(always)]
+    fn wait (self) -> Result<Self::Result> {
+        self.inner.wait()
+    }
+
+    #[inline(always)]
+    fn wait_all (iter: impl IntoIterator<Item = Self>) -> Result<alloc::vec::Vec<Self::Result>> {
+        let iter = iter.into_iter().map(|x| x.inner);
+        BaseEvent::wait_all(iter)
+    }
+}
+
+#[cfg(feature = "async")]
+impl futures::Future for WriteImage<'_, '_> {
+    type Output = Result<()>;
+
+    #[inline(always)]
+    fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<Self::Output> {
+        core::pin::Pin::new(&mut self.inner).poll(cx)
+    }
+}
+
+impl AsRef<BaseEvent> for WriteImage<'_, '_> {
+    #[inline(always)]
+    fn as_ref(&self) -> &BaseEvent {
+        &self.inner
+    }
+}
+
+/// Event that copies a region from one OpenCL image to another
+#[repr(transparent)]
+pub struct CopyImage<'a, 'b> {
+    inner: BaseEvent,
+    phtm: PhantomData<(&'a (), &'b ())>
+}
+
+impl<'a, 'b> CopyImage<'a, 'b> {
+    pub fn new<T: ImagePixel, D: ImageDim, E: ImageDim> (queue: &CommandQueue, src: &'a Image<T, D>, src_origin: [usize; 3], dst: &'b mut Image<T, E>, dst_origin: [usize; 3], region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Self> {
+        let wait = wait.into_iter().map(|x| x.as_ref().0).collect::<Vec<_>>();
+        let wait_len = u32::try_from(wait.len()).unwrap();
+        let wait = match wait_len {
+            0 => core::ptr::null(),
+            _ => wait.as_ptr()
+        };
+
+        let mut event : cl_event = core::ptr::null_mut();
+        let err = unsafe { clEnqueueCopyImage(queue.0, src.0, dst.0, src_origin.as_ptr(), dst_origin.as_ptr(), region.as_ptr(), wait_len, wait, &mut event) };
+
+        if err == 0 {
+            let inner = BaseEvent::new(event)?;
+            return Ok(Self { inner, phtm: PhantomData });
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "error-stack")] {
+                let err = Error::from(err);
+                let report = error_stack::Report::new(err);
+
+                let report = match err {
+                    Error::InvalidCommandQueue => report.attach_printable(format!("'{:?}' is not a valid command-queue", queue.0)),
+                    Error::InvalidContext => report.attach_printable("the context associated with the command queue and memory objects are not the same or the context associated with command queue and events in the event wait list are not the same"),
+                    Error::InvalidMemObject => report.attach_printable(format!("'{:?}' and/or '{:?}' are not a valid image", src.0, dst.0)),
+                    Error::InvalidValue => report.attach_printable("the region being copied is out of bounds or the origin and region are not valid for the image type"),
+                    Error::InvalidEventWaitList => report.attach_printable("event objects in the event wait list are not valid events"),
+                    Error::ImageFormatMismatch => report.attach_printable("source and destination images don't use the same image format"),
+                    Error::MemCopyOverlap => report.attach_printable("source and destination are the same image and the regions overlap"),
+                    Error::InvalidImageSize => report.attach_printable("image dimensions are not supported by the device associated with the command queue"),
+                    Error::ImageFormatNotSupported => report.attach_printable("image format is not supported by the device associated with the command queue"),
+                    Error::InvalidOperation => report.attach_printable("the device associated with the command queue does not support images"),
+                    Error::MemObjectAllocationFailure => report.attach_printable("there is a failure to allocate memory for data store associated with the image"),
+                    Error::OutOfResources => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the device"),
+                    Error::OutOfHostMemory => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the host"),
+                    _ => report
+                };
+
+                Err(report)
+            } else {
+                Err(Error::from(err))
+            }
+        }
+    }
+}
+
+impl Event for CopyImage<'_, '_> {
+    type Result = ();
+
+    #[inline(always)]
+    fn wait (self) -> Result<Self::Result> {
+        self.inner.wait()
+    }
+
+    #[inline(always)]
+    fn wait_all (iter: impl IntoIterator<Item = Self>) -> Result<alloc::vec::Vec<Self::Result>> {
+        let iter = iter.into_iter().map(|x| x.inner);
+        BaseEvent::wait_all(iter)
+    }
+}
+
+#[cfg(feature = "async")]
+impl futures::Future for CopyImage<'_, '_> {
+    type Output = Result<()>;
+
+    #[inline(always)]
+    fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<Self::Output> {
+        core::pin::Pin::new(&mut self.inner).poll(cx)
+    }
+}
+
+impl AsRef<BaseEvent> for CopyImage<'_, '_> {
+    #[inline(always)]
+    fn as_ref(&self) -> &BaseEvent {
+        &self.inner
+    }
+}
+
+/// Event that copies a region of an OpenCL image into an OpenCL buffer
+#[repr(transparent)]
+pub struct CopyImageToBuffer<'a, 'b> {
+    inner: BaseEvent,
+    phtm: PhantomData<(&'a (), &'b ())>
+}
+
+impl<'a, 'b> CopyImageToBuffer<'a, 'b> {
+    /// Copies the ```region``` at ```origin``` into ```dst```, tightly packed and starting at element ```offset```.
+    pub fn new<T: ImagePixel, D: ImageDim> (queue: &CommandQueue, src: &'a Image<T, D>, origin: [usize; 3], region: [usize; 3], dst: &'b mut MemBuffer<T>, offset: usize, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Self> {
+        let wait = wait.into_iter().map(|x| x.as_ref().0).collect::<Vec<_>>();
+        let wait_len = u32::try_from(wait.len()).unwrap();
+        let wait = match wait_len {
+            0 => core::ptr::null(),
+            _ => wait.as_ptr()
+        };
+
+        let offset = offset.checked_mul(core::mem::size_of::<T>()).expect("Integer overflow. Too many elements in buffer");
+        let mut event : cl_event = core::ptr::null_mut();
+        let err = unsafe { clEnqueueCopyImageToBuffer(queue.0, src.0, dst.0, origin.as_ptr(), region.as_ptr(), offset, wait_len, wait, &mut event) };
+
+        if err == 0 {
+            let inner = BaseEvent::new(event)?;
+            return Ok(Self { inner, phtm: PhantomData });
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "error-stack")] {
+                let err = Error::from(err);
+                let report = error_stack::Report::new(err);
+
+                let report = match err {
+                    Error::InvalidCommandQueue => report.attach_printable(format!("'{:?}' is not a valid command-queue", queue.0)),
+                    Error::InvalidContext => report.attach_printable("the context associated with the command queue and memory objects are not the same or the context associated with command queue and events in the event wait list are not the same"),
+                    Error::InvalidMemObject => report.attach_printable(format!("'{:?}' is not a valid image and/or '{:?}' is not a valid buffer", src.0, dst.0)),
+                    Error::InvalidValue => report.attach_printable("the region being copied is out of bounds or the origin and region are not valid for the image type"),
+                    Error::InvalidEventWaitList => report.attach_printable("event objects in the event wait list are not valid events"),
+                    Error::MisalignedSubBufferOffset => report.attach_printable("buffer is a sub-buffer whose offset is not aligned to the base address alignment of the device"),
+                    Error::InvalidImageSize => report.attach_printable("image dimensions are not supported by the device associated with the command queue"),
+                    Error::ImageFormatNotSupported => report.attach_printable("image format is not supported by the device associated with the command queue"),
+                    Error::InvalidOperation => report.attach_printable("the device associated with the command queue does not support images"),
+                    Error::MemObjectAllocationFailure => report.attach_printable("there is a failure to allocate memory for data store associated with the image"),
+                    Error::OutOfResources => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the device"),
+                    Error::OutOfHostMemory => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the host"),
+                    _ => report
+                };
+
+                Err(report)
+            } else {
+                Err(Error::from(err))
+            }
+        }
+    }
+}
+
+impl Event for CopyImageToBuffer<'_, '_> {
+    type Result = ();
+
+    #[inline(always)]
+    fn wait (self) -> Result<Self::Result> {
+        self.inner.wait()
+    }
+
+    #[inline(always)]
+    fn wait_all (iter: impl IntoIterator<Item = Self>) -> Result<alloc::vec::Vec<Self::Result>> {
+        let iter = iter.into_iter().map(|x| x.inner);
+        BaseEvent::wait_all(iter)
+    }
+}
+
+#[cfg(feature = "async")]
+impl futures::Future for CopyImageToBuffer<'_, '_> {
+    type Output = Result<()>;
+
+    #[inline(always)]
+    fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<Self::Output> {
+        core::pin::Pin::new(&mut self.inner).poll(cx)
+    }
+}
+
+impl AsRef<BaseEvent> for CopyImageToBuffer<'_, '_> {
+    #[inline(always)]
+    fn as_ref(&self) -> &BaseEvent {
+        &self.inner
+    }
+}
+
+/// Event that copies the contents of an OpenCL buffer into a region of an OpenCL image
+#[repr(transparent)]
+pub struct CopyBufferToImage<'a, 'b> {
+    inner: BaseEvent,
+    phtm: PhantomData<(&'a (), &'b ())>
+}
+
+impl<'a, 'b> CopyBufferToImage<'a, 'b> {
+    /// Copies the tightly packed pixels of ```src```, starting at element ```offset```, into the ```region``` at ```origin```.
+    pub fn new<T: ImagePixel, D: ImageDim> (queue: &CommandQueue, src: &'a MemBuffer<T>, offset: usize, dst: &'b mut Image<T, D>, origin: [usize; 3], region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Self> {
+        let wait = wait.into_iter().map(|x| x.as_ref().0).collect::<Vec<_>>();
+        let wait_len = u32::try_from(wait.len()).unwrap();
+        let wait = match wait_len {
+            0 => core::ptr::null(),
+            _ => wait.as_ptr()
+        };
+
+        let offset = offset.checked_mul(core::mem::size_of::<T>()).expect("Integer overflow. Too many elements in buffer");
+        let mut event : cl_event = core::ptr::null_mut();
+        let err = unsafe { clEnqueueCopyBufferToImage(queue.0, src.0, dst.0, offset, origin.as_ptr(), region.as_ptr(), wait_len, wait, &mut event) };
+
+        if err == 0 {
+            let inner = BaseEvent::new(event)?;
+            return Ok(Self { inner, phtm: PhantomData });
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "error-stack")] {
+                let err = Error::from(err);
+                let report = error_stack::Report::new(err);
+
+                let report = match err {
+                    Error::InvalidCommandQueue => report.attach_printable(format!("'{:?}' is not a valid command-queue", queue.0)),
+                    Error::InvalidContext => report.attach_printable("the context associated with the command queue and memory objects are not the same or the context associated with command queue and events in the event wait list are not the same"),
+                    Error::InvalidMemObject => report.attach_printable(format!("'{:?}' is not a valid buffer and/or '{:?}' is not a valid image", src.0, dst.0)),
+                    Error::InvalidValue => report.attach_printable("the region being copied is out of bounds or the origin and region are not valid for the image type"),
+                    Error::InvalidEventWaitList => report.attach_printable("event objects in the event wait list are not valid events"),
+                    Error::MisalignedSubBufferOffset => report.attach_printable("buffer is a sub-buffer whose offset is not aligned to the base address alignment of the device"),
+                    Error::InvalidImageSize => report.attach_printable("image dimensions are not supported by the device associated with the command queue"),
+                    Error::ImageFormatNotSupported => report.attach_printable("image format is not supported by the device associated with the command queue"),
+                    Error::InvalidOperation => report.attach_printable("the device associated with the command queue does not support images"),
+                    Error::MemObjectAllocationFailure => report.attach_printable("there is a failure to allocate memory for data store associated with the image"),
+                    Error::OutOfResources => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the device"),
+                    Error::OutOfHostMemory => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the host"),
+                    _ => report
+                };
+
+                Err(report)
+            } else {
+                Err(Error::from(err))
+            }
+        }
+    }
+}
+
+impl Event for CopyBufferToImage<'_, '_> {
+    type Result = ();
+
+    #[inline(always)]
+    fn wait (self) -> Result<Self::Result> {
+        self.inner.wait()
+    }
+
+    #[inline(always)]
+    fn wait_all (iter: impl IntoIterator<Item = Self>) -> Result<alloc::vec::Vec<Self::Result>> {
+        let iter = iter.into_iter().map(|x| x.inner);
+        BaseEvent::wait_all(iter)
+    }
+}
+
+#[cfg(feature = "async")]
+impl futures::Future for CopyBufferToImage<'_, '_> {
+    type Output = Result<()>;
+
+    #[inline(always)]
+    fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<Self::Output> {
+        core::pin::Pin::new(&mut self.inner).poll(cx)
+    }
+}
+
+impl AsRef<BaseEvent> for CopyBufferToImage<'_, '_> {
+    #[inline(always)]
+    fn as_ref(&self) -> &BaseEvent {
+        &self.inner
+    }
+}
+
+/// Event that fills a region of an OpenCL image with a color
+#[repr(transparent)]
+pub struct FillImage<'a> {
+    inner: BaseEvent,
+    phtm: PhantomData<&'a ()>
+}
+
+impl<'a> FillImage<'a> {
+    /// Sets every pixel of the ```region``` at ```origin``` to ```color```. The color is copied when the command is enqueued.
+    pub fn new<T: ImagePixel, D: ImageDim> (queue: &CommandQueue, color: T::Color, dst: &'a mut Image<T, D>, origin: [usize; 3], region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Self> {
+        let wait = wait.into_iter().map(|x| x.as_ref().0).collect::<Vec<_>>();
+        let wait_len = u32::try_from(wait.len()).unwrap();
+        let wait = match wait_len {
+            0 => core::ptr::null(),
+            _ => wait.as_ptr()
+        };
+
+        let mut event : cl_event = core::ptr::null_mut();
+        let err = unsafe { clEnqueueFillImage(queue.0, dst.0, core::ptr::addr_of!(color).cast(), origin.as_ptr(), region.as_ptr(), wait_len, wait, &mut event) };
+
+        if err == 0 {
+            let inner = BaseEvent::new(event)?;
+            return Ok(Self { inner, phtm: PhantomData });
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "error-stack")] {
+                let err = Error::from(err);
+                let report = error_stack::Report::new(err);
+
+                let report = match err {
+                    Error::InvalidCommandQueue => report.attach_printable(format!("'{:?}' is not a valid command-queue", queue.0)),
+                    Error::InvalidContext => report.attach_printable("the context associated with the command queue and memory objects are not the same or the context associated with command queue and events in the event wait list are not the same"),
+                    Error::InvalidMemObject => report.attach_printable(format!("'{:?}' is not a valid image", dst.0)),
+                    Error::InvalidValue => report.attach_printable("the region being filled is out of bounds or the origin and region are not valid for the image type"),
+                    Error::InvalidEventWaitList => report.attach_printable("event objects in the event wait list are not valid events"),
+                    Error::InvalidImageSize => report.attach_printable("image dimensions are not supported by the device associated with the command queue"),
+                    Error::ImageFormatNotSupported => report.attach_printable("image format is not supported by the device associated with the command queue"),
+                    Error::InvalidOperation => report.attach_printable("the device associated with the command queue does not support images"),
+                    Error::MemObjectAllocationFailure => report.attach_printable("there is a failure to allocate memory for data store associated with the image"),
+                    Error::OutOfResources => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the device"),
+                    Error::OutOfHostMemory => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the host"),
+                    _ => report
+                };
+
+                Err(report)
+            } else {
+                Err(Error::from(err))
+            }
+        }
+    }
+}
+
+impl Event for FillImage<'_> {
+    type Result = ();
+
+    #[inline(always)]
+    fn wait (self) -> Result<Self::Result> {
+        self.inner.wait()
+    }
+
+    #[inline(always)]
+    fn wait_all (iter: impl IntoIterator<Item = Self>) -> Result<alloc::vec::Vec<Self::Result>> {
+        let iter = iter.into_iter().map(|x| x.inner);
+        BaseEvent::wait_all(iter)
+    }
+}
+
+#[cfg(feature = "async")]
+impl futures::Future for FillImage<'_> {
+    type Output = Result<()>;
+
+    #[inline(always)]
+    fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<Self::Output> {
+        core::pin::Pin::new(&mut self.inner).poll(cx)
+    }
+}
+
+impl AsRef<BaseEvent> for FillImage<'_> {
+    #[inline(always)]
+    fn as_ref(&self) -> &BaseEvent {
+        &self.inner
+    }
+}
+
+#[inline]
+fn check_host (region: [usize; 3], len: usize) -> Result<()> {
+    let required = region.iter().try_fold(1usize, |x, y| x.checked_mul(*y));
+    if required.is_none_or(|required| required > len) {
+        #[cfg(feature = "error-stack")]
+        return Err(error_stack::Report::new(Error::InvalidValue).attach_printable(format!("host slice has {len} pixels, but the region {region:?} requires more")));
+        #[cfg(not(feature = "error-stack"))]
+        return Err(Error::InvalidValue);
+    }
+
+    Ok(())
+}
\ No newline at end of file
@@ -1,9 +1,9 @@
 use alloc::vec::Vec;
-use opencl_sys::{CL_COMMAND_NDRANGE_KERNEL, CL_COMMAND_TASK, CL_COMMAND_NATIVE_KERNEL, CL_COMMAND_READ_BUFFER, CL_COMMAND_WRITE_BUFFER, CL_COMMAND_COPY_BUFFER, CL_COMMAND_READ_IMAGE, CL_COMMAND_WRITE_IMAGE, CL_COMMAND_COPY_IMAGE, CL_COMMAND_COPY_IMAGE_TO_BUFFER, CL_COMMAND_COPY_BUFFER_TO_IMAGE, CL_COMMAND_MAP_BUFFER, CL_COMMAND_MAP_IMAGE, CL_COMMAND_UNMAP_MEM_OBJECT, CL_COMMAND_MARKER, CL_COMMAND_ACQUIRE_GL_OBJECTS, CL_COMMAND_RELEASE_GL_OBJECTS, CL_COMPLETE, CL_RUNNING, CL_SUBMITTED, CL_QUEUED};
+use opencl_sys::{CL_COMMAND_NDRANGE_KERNEL, CL_COMMAND_TASK, CL_COMMAND_NATIVE_KERNEL, CL_COMMAND_READ_BUFFER, CL_COMMAND_WRITE_BUFFER, CL_COMMAND_COPY_BUFFER, CL_COMMAND_FILL_BUFFER, CL_COMMAND_READ_BUFFER_RECT, CL_COMMAND_WRITE_BUFFER_RECT, CL_COMMAND_COPY_BUFFER_RECT, CL_COMMAND_READ_IMAGE, CL_COMMAND_WRITE_IMAGE, CL_COMMAND_COPY_IMAGE, CL_COMMAND_COPY_IMAGE_TO_BUFFER, CL_COMMAND_COPY_BUFFER_TO_IMAGE, CL_COMMAND_FILL_IMAGE, CL_COMMAND_MIGRATE_MEM_OBJECTS, CL_COMMAND_MAP_BUFFER, CL_COMMAND_MAP_IMAGE, CL_COMMAND_UNMAP_MEM_OBJECT, CL_COMMAND_MARKER, CL_COMMAND_ACQUIRE_GL_OBJECTS, CL_COMMAND_RELEASE_GL_OBJECTS, CL_COMPLETE, CL_RUNNING, CL_SUBMITTED, CL_QUEUED};
 use crate::{prelude::{Result, CommandQueue}};
 use self::various::{Map, Swap, Then};
 
-flat_mod!(base, user, buffer);
+flat_mod!(base, user, buffer, rect, image);
 #[cfg(feature = "async")]
 flat_mod!(future);
 pub mod various;
@@ -129,11 +129,17 @@ pub enum CommandType {
     ReadBuffer = CL_COMMAND_READ_BUFFER,
     WriteBuffer = CL_COMMAND_WRITE_BUFFER,
     CopyBuffer = CL_COMMAND_COPY_BUFFER,
+    FillBuffer = CL_COMMAND_FILL_BUFFER,
+    ReadBufferRect = CL_COMMAND_READ_BUFFER_RECT,
+    WriteBufferRect = CL_COMMAND_WRITE_BUFFER_RECT,
+    CopyBufferRect = CL_COMMAND_COPY_BUFFER_RECT,
     ReadImage = CL_COMMAND_READ_IMAGE,
     WriteImage = CL_COMMAND_WRITE_IMAGE,
     CopyImage = CL_COMMAND_COPY_IMAGE,
     CopyImageToBuffer = CL_COMMAND_COPY_IMAGE_TO_BUFFER,
     CopyBufferToImage = CL_COMMAND_COPY_BUFFER_TO_IMAGE,
+    FillImage = CL_COMMAND_FILL_IMAGE,
+    MigrateMemObjects = CL_COMMAND_MIGRATE_MEM_OBJECTS,
     MapBuffer = CL_COMMAND_MAP_BUFFER,
     MapImage = CL_COMMAND_MAP_IMAGE,
     UnmapMemObject = CL_COMMAND_UNMAP_MEM_OBJECT,
@@ -0,0 +1,283 @@
+use core::marker::PhantomData;
+use alloc::vec::Vec;
+use opencl_sys::{cl_event, clEnqueueReadBufferRect, clEnqueueWriteBufferRect, clEnqueueCopyBufferRect};
+use crate::{prelude::{Result, Error, CommandQueue}, buffer::{MemBuffer, DeviceElement, RectLayout, region_bytes}};
+use super::{BaseEvent, Event};
+
+#[cfg(feature = "error-stack")]
+use alloc::format;
+
+/// Event that reads a rectangular region of an OpenCL buffer into host memory
+#[repr(transparent)]
+pub struct ReadBufferRect<'a, 'b> {
+    inner: BaseEvent,
+    phtm: PhantomData<(&'a (), &'b ())>
+}
+
+impl<'a, 'b> ReadBufferRect<'a, 'b> {
+    /// Reads the ```region``` (```[columns, rows, slices]```, in elements) located at ```src_layout``` inside ```src``` into ```dst_layout``` inside ```dst```.
+    /// # Errors
+    /// Returns [```Error::InvalidValue```] if ```dst``` is too small to hold the region at ```dst_layout```.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<T: DeviceElement> (queue: &CommandQueue, blocking: bool, src: &'a MemBuffer<T>, src_layout: RectLayout, dst: &'b mut [T], dst_layout: RectLayout, region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Self> {
+        dst_layout.check_host(region, dst.len())?;
+        let (buffer_origin, buffer_row_pitch, buffer_slice_pitch) = src_layout.to_bytes::<T>(region)?;
+        let (host_origin, host_row_pitch, host_slice_pitch) = dst_layout.to_bytes::<T>(region)?;
+        let region = region_bytes::<T>(region)?;
+
+        let wait = wait.into_iter().map(|x| x.as_ref().0).collect::<Vec<_>>();
+        let wait_len = u32::try_from(wait.len()).unwrap();
+        let wait = match wait_len {
+            0 => core::ptr::null(),
+            _ => wait.as_ptr()
+        };
+
+        let mut event : cl_event = core::ptr::null_mut();
+        let err = unsafe {
+            clEnqueueReadBufferRect(queue.0, src.0, opencl_sys::cl_bool::from(blocking), buffer_origin.as_ptr(), host_origin.as_ptr(), region.as_ptr(), buffer_row_pitch, buffer_slice_pitch, host_row_pitch, host_slice_pitch, dst.as_mut_ptr().cast(), wait_len, wait, &mut event)
+        };
+
+        if err == 0 {
+            let inner = BaseEvent::new(event)?;
+            return Ok(Self { inner, phtm: PhantomData });
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "error-stack")] {
+                let err = Error::from(err);
+                let report = error_stack::Report::new(err);
+
+                let report = match err {
+                    Error::InvalidCommandQueue => report.attach_printable(format!("'{:?}' is not a valid command-queue", queue.0)),
+                    Error::InvalidContext => report.attach_printable("the context associated with the command queue and buffer are not the same or the context associated with command queue and events in the event wait list are not the same"),
+                    Error::InvalidMemObject => report.attach_printable(format!("'{:?}' is not a valid buffer", src.0)),
+                    Error::InvalidValue => report.attach_printable("the region being read is out of bounds, any region dimension is zero or the pitches are invalid"),
+                    Error::InvalidEventWaitList => report.attach_printable("event objects in the event wait list are not valid events"),
+                    Error::MisalignedSubBufferOffset => report.attach_printable("buffer is a sub-buffer whose offset is not aligned to the base address alignment of the device"),
+                    Error::MemObjectAllocationFailure => report.attach_printable("there is a failure to allocate memory for data store associated with buffer"),
+                    Error::OutOfResources => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the device"),
+                    Error::OutOfHostMemory => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the host"),
+                    _ => report
+                };
+
+                Err(report)
+            } else {
+                Err(Error::from(err))
+            }
+        }
+    }
+}
+
+impl Event for ReadBufferRect<'_, '_> {
+    type Result = ();
+
+    #[inline(always)]
+    fn wait (self) -> Result<Self::Result> {
+        self.inner.wait()
+    }
+
+    #[inline(always)]
+    fn wait_all (iter: impl IntoIterator<Item = Self>) -> Result<alloc::vec::Vec<Self::Result>> {
+        let iter = iter.into_iter().map(|x| x.inner);
+        BaseEvent::wait_all(iter)
+    }
+}
+
+#[cfg(feature = "async")]
+impl futures::Future for ReadBufferRect<'_, '_> {
+    type Output = Result<()>;
+
+    #[inline(always)]
+    fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<Self::Output> {
+        core::pin::Pin::new(&mut self.inner).poll(cx)
+    }
+}
+
+impl AsRef<BaseEvent> for ReadBufferRect<'_, '_> {
+    #[inline(always)]
+    fn as_ref(&self) -> &BaseEvent {
+        &self.inner
+    }
+}
+
+/// Event that writes a rectangular region of host memory into an OpenCL buffer
+#[repr(transparent)]
+pub struct WriteBufferRect<'a, 'b> {
+    inner: BaseEvent,
+    phtm: PhantomData<(&'a (), &'b ())>
+}
+
+impl<'a, 'b> WriteBufferRect<'a, 'b> {
+    /// Writes the ```region``` (```[columns, rows, slices]```, in elements) located at ```src_layout``` inside ```src``` into ```dst_layout``` inside ```dst```.
+    /// # Errors
+    /// Returns [```Error::InvalidValue```] if ```src``` is too small to hold the region at ```src_layout```.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<T: DeviceElement> (queue: &CommandQueue, blocking: bool, src: &'a [T], src_layout: RectLayout, dst: &'b mut MemBuffer<T>, dst_layout: RectLayout, region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Self> {
+        src_layout.check_host(region, src.len())?;
+        let (buffer_origin, buffer_row_pitch, buffer_slice_pitch) = dst_layout.to_bytes::<T>(region)?;
+        let (host_origin, host_row_pitch, host_slice_pitch) = src_layout.to_bytes::<T>(region)?;
+        let region = region_bytes::<T>(region)?;
+
+        let wait = wait.into_iter().map(|x| x.as_ref().0).collect::<Vec<_>>();
+        let wait_len = u32::try_from(wait.len()).unwrap();
+        let wait = match wait_len {
+            0 => core::ptr::null(),
+            _ => wait.as_ptr()
+        };
+
+        let mut event : cl_event = core::ptr::null_mut();
+        let err = unsafe {
+            clEnqueueWriteBufferRect(queue.0, dst.0, opencl_sys::cl_bool::from(blocking), buffer_origin.as_ptr(), host_origin.as_ptr(), region.as_ptr(), buffer_row_pitch, buffer_slice_pitch, host_row_pitch, host_slice_pitch, src.as_ptr().cast(), wait_len, wait, &mut event)
+        };
+
+        if err == 0 {
+            let inner = BaseEvent::new(event)?;
+            return Ok(Self { inner, phtm: PhantomData });
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "error-stack")] {
+                let err = Error::from(err);
+                let report = error_stack::Report::new(err);
+
+                let report = match err {
+                    Error::InvalidCommandQueue => report.attach_printable(format!("'{:?}' is not a valid command-queue", queue.0)),
+                    Error::InvalidContext => report.attach_printable("the context associated with the command queue and buffer are not the same or the context associated with command queue and events in the event wait list are not the same"),
+                    Error::InvalidMemObject => report.attach_printable(format!("'{:?}' is not a valid buffer", dst.0)),
+                    Error::InvalidValue => report.attach_printable("the region being written is out of bounds, any region dimension is zero or the pitches are invalid"),
+                    Error::InvalidEventWaitList => report.attach_printable("event objects in the event wait list are not valid events"),
+                    Error::MisalignedSubBufferOffset => report.attach_printable("buffer is a sub-buffer whose offset is not aligned to the base address alignment of the device"),
+                    Error::MemObjectAllocationFailure => report.attach_printable("there is a failure to allocate memory for data store associated with buffer"),
+                    Error::OutOfResources => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the device"),
+                    Error::OutOfHostMemory => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the host"),
+                    _ => report
+                };
+
+                Err(report)
+            } else {
+                Err(Error::from(err))
+            }
+        }
+    }
+}
+
+impl Event for WriteBufferRect<'_, '_> {
+    type Result = ();
+
+    #[inline(always)]
+    fn wait (self) -> Result<Self::Result> {
+        self.inner.wait()
+    }
+
+    #[inline(always)]
+    fn wait_all (iter: impl IntoIterator<Item = Self>) -> Result<alloc::vec::Vec<Self::Result>> {
+        let iter = iter.into_iter().map(|x| x.inner);
+        BaseEvent::wait_all(iter)
+    }
+}
+
+#[cfg(feature = "async")]
+impl futures::Future for WriteBufferRect<'_, '_> {
+    type Output = Result<()>;
+
+    #[inline(always)]
+    fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<Self::Output> {
+        core::pin::Pin::new(&mut self.inner).poll(cx)
+    }
+}
+
+impl AsRef<BaseEvent> for WriteBufferRect<'_, '_> {
+    #[inline(always)]
+    fn as_ref(&self) -> &BaseEvent {
+        &self.inner
+    }
+}
+
+/// Event that copies a rectangular region from one OpenCL buffer to another
+#[repr(transparent)]
+pub struct CopyBufferRect<'a, 'b> {
+    inner: BaseEvent,
+    phtm: PhantomData<(&'a (), &'b ())>
+}
+
+impl<'a, 'b> CopyBufferRect<'a, 'b> {
+    /// Copies the ```region``` (```[columns, rows, slices]```, in elements) located at ```src_layout``` inside ```src``` into ```dst_layout``` inside ```dst```.
+    pub fn new<T: DeviceElement> (queue: &CommandQueue, src: &'a MemBuffer<T>, src_layout: RectLayout, dst: &'b mut MemBuffer<T>, dst_layout: RectLayout, region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Self> {
+        let (src_origin, src_row_pitch, src_slice_pitch) = src_layout.to_bytes::<T>(region)?;
+        let (dst_origin, dst_row_pitch, dst_slice_pitch) = dst_layout.to_bytes::<T>(region)?;
+        let region = region_bytes::<T>(region)?;
+
+        let wait = wait.into_iter().map(|x| x.as_ref().0).collect::<Vec<_>>();
+        let wait_len = u32::try_from(wait.len()).unwrap();
+        let wait = match wait_len {
+            0 => core::ptr::null(),
+            _ => wait.as_ptr()
+        };
+
+        let mut event : cl_event = core::ptr::null_mut();
+        let err = unsafe {
+            clEnqueueCopyBufferRect(queue.0, src.0, dst.0, src_origin.as_ptr(), dst_origin.as_ptr(), region.as_ptr(), src_row_pitch, src_slice_pitch, dst_row_pitch, dst_slice_pitch, wait_len, wait, &mut event)
+        };
+
+        if err == 0 {
+            let inner = BaseEvent::new(event)?;
+            return Ok(Self { inner, phtm: PhantomData });
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "error-stack")] {
+                let err = Error::from(err);
+                let report = error_stack::Report::new(err);
+
+                let report = match err {
+                    Error::InvalidCommandQueue => report.attach_printable(format!("'{:?}' is not a valid command-queue", queue.0)),
+                    Error::InvalidContext => report.attach_printable("the context associated with the command queue and buffer are not the same or the context associated with command queue and events in the event wait list are not the same"),
+                    Error::InvalidMemObject => report.attach_printable(format!("'{:?}' and/or '{:?}' are not a valid buffer", src.0, dst.0)),
+                    Error::InvalidValue => report.attach_printable("the region being copied is out of bounds, any region dimension is zero or the pitches are invalid"),
+                    Error::InvalidEventWaitList => report.attach_printable("event objects in the event wait list are not valid events"),
+                    Error::MisalignedSubBufferOffset => report.attach_printable("buffer is a sub-buffer whose offset is not aligned to the base address alignment of the device"),
+                    Error::MemObjectAllocationFailure => report.attach_printable("there is a failure to allocate memory for data store associated with buffer"),
+                    Error::OutOfResources => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the device"),
+                    Error::OutOfHostMemory => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the host"),
+                    _ => report
+                };
+
+                Err(report)
+            } else {
+                Err(Error::from(err))
+            }
+        }
+    }
+}
+
+impl Event for CopyBufferRect<'_, '_> {
+    type Result = ();
+
+    #[inline(always)]
+    fn wait (self) -> Result<Self::Result> {
+        self.inner.wait()
+    }
+
+    #[inline(always)]
+    fn wait_all (iter: impl IntoIterator<Item = Self>) -> Result<alloc::vec::Vec<Self::Result>> {
+        let iter = iter.into_iter().map(|x| x.inner);
+        BaseEvent::wait_all(iter)
+    }
+}
+
+#[cfg(feature = "async")]
+impl futures::Future for CopyBufferRect<'_, '_> {
+    type Output = Result<()>;
+
+    #[inline(always)]
+    fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<Self::Output> {
+        core::pin::Pin::new(&mut self.inner).poll(cx)
+    }
+}
+
+impl AsRef<BaseEvent> for CopyBufferRect<'_, '_> {
+    #[inline(always)]
+    fn as_ref(&self) -> &BaseEvent {
+        &self.inner
+    }
+}
\ No newline at end of file
@@ -0,0 +1,497 @@
+use core::{marker::PhantomData, mem::MaybeUninit, ptr::NonNull, fmt::Debug};
+use alloc::vec::Vec;
+use opencl_sys::{cl_mem, cl_image_desc, cl_image_format, cl_image_info, cl_mem_info, clCreateImage, clReleaseMemObject, clGetImageInfo, clGetMemObjectInfo, clRetainContext, CL_IMAGE_FORMAT, CL_IMAGE_ELEMENT_SIZE, CL_IMAGE_ROW_PITCH, CL_IMAGE_SLICE_PITCH, CL_IMAGE_WIDTH, CL_IMAGE_HEIGHT, CL_IMAGE_DEPTH, CL_IMAGE_ARRAY_SIZE, CL_MEM_FLAGS, CL_MEM_CONTEXT};
+use crate::{prelude::{Result, Error, Context, CommandQueue, BaseEvent, MemBuffer}, buffer::{MemFlag, MemObject, MemObjectType}, event::{ReadImage, WriteImage, CopyImage, CopyImageToBuffer, CopyBufferToImage, FillImage, Event, various::Map}};
+use super::{ImagePixel, ImageFormat};
+
+#[cfg(feature = "error-stack")]
+use alloc::format;
+
+/// 2D image
+pub type Image2D<T> = Image<T, Dim2>;
+/// 3D image
+pub type Image3D<T> = Image<T, Dim3>;
+/// Array of 2D images
+pub type ImageArray<T> = Image<T, Array2>;
+
+/// Dimensionality of an image
+pub trait ImageDim: 'static + Unpin {
+    const TYPE: MemObjectType;
+}
+
+/// Marker for 2D images
+pub enum Dim2 {}
+/// Marker for 3D images
+pub enum Dim3 {}
+/// Marker for arrays of 2D images
+pub enum Array2 {}
+
+impl ImageDim for Dim2 {
+    const TYPE: MemObjectType = MemObjectType::Image2D;
+}
+
+impl ImageDim for Dim3 {
+    const TYPE: MemObjectType = MemObjectType::Image3D;
+}
+
+impl ImageDim for Array2 {
+    const TYPE: MemObjectType = MemObjectType::Image2DArray;
+}
+
+/// OpenCL image object. Origins and regions are expressed as ```[x, y, z]```, where ```z``` is the depth of 3D images or the layer of image arrays, and must be ```0``` (or ```1``` for regions) for 2D images.
+pub struct Image<T: ImagePixel, D: ImageDim> (pub(crate) cl_mem, PhantomData<(T, D)>);
+
+impl<T: ImagePixel> Image<T, Dim2> {
+    /// # Safety
+    /// The contents of the image are uninitialized
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub unsafe fn uninit (width: usize, height: usize, flags: MemFlag) -> Result<Self> {
+        Self::uninit_with_context(Context::default(), width, height, flags)
+    }
+
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn new (src: &[T], width: usize, height: usize, flags: MemFlag) -> Result<Self> {
+        Self::with_context(Context::default(), flags, src, width, height)
+    }
+
+    /// # Safety
+    /// The contents of the image are uninitialized
+    #[inline(always)]
+    pub unsafe fn uninit_with_context (ctx: &Context, width: usize, height: usize, flags: MemFlag) -> Result<Self> {
+        Self::create(ctx, flags, [width, height, 1], None)
+    }
+
+    #[inline(always)]
+    pub fn with_context (ctx: &Context, flags: MemFlag, src: &[T], width: usize, height: usize) -> Result<Self> {
+        Self::create_from_slice(ctx, flags, src, [width, height, 1])
+    }
+}
+
+impl<T: ImagePixel> Image<T, Dim3> {
+    /// # Safety
+    /// The contents of the image are uninitialized
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub unsafe fn uninit (width: usize, height: usize, depth: usize, flags: MemFlag) -> Result<Self> {
+        Self::uninit_with_context(Context::default(), width, height, depth, flags)
+    }
+
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn new (src: &[T], width: usize, height: usize, depth: usize, flags: MemFlag) -> Result<Self> {
+        Self::with_context(Context::default(), flags, src, width, height, depth)
+    }
+
+    /// # Safety
+    /// The contents of the image are uninitialized
+    #[inline(always)]
+    pub unsafe fn uninit_with_context (ctx: &Context, width: usize, height: usize, depth: usize, flags: MemFlag) -> Result<Self> {
+        Self::create(ctx, flags, [width, height, depth], None)
+    }
+
+    #[inline(always)]
+    pub fn with_context (ctx: &Context, flags: MemFlag, src: &[T], width: usize, height: usize, depth: usize) -> Result<Self> {
+        Self::create_from_slice(ctx, flags, src, [width, height, depth])
+    }
+}
+
+impl<T: ImagePixel> Image<T, Array2> {
+    /// # Safety
+    /// The contents of the image are uninitialized
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub unsafe fn uninit (width: usize, height: usize, layers: usize, flags: MemFlag) -> Result<Self> {
+        Self::uninit_with_context(Context::default(), width, height, layers, flags)
+    }
+
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn new (src: &[T], width: usize, height: usize, layers: usize, flags: MemFlag) -> Result<Self> {
+        Self::with_context(Context::default(), flags, src, width, height, layers)
+    }
+
+    /// # Safety
+    /// The contents of the image are uninitialized
+    #[inline(always)]
+    pub unsafe fn uninit_with_context (ctx: &Context, width: usize, height: usize, layers: usize, flags: MemFlag) -> Result<Self> {
+        Self::create(ctx, flags, [width, height, layers], None)
+    }
+
+    #[inline(always)]
+    pub fn with_context (ctx: &Context, flags: MemFlag, src: &[T], width: usize, height: usize, layers: usize) -> Result<Self> {
+        Self::create_from_slice(ctx, flags, src, [width, height, layers])
+    }
+}
+
+impl<T: ImagePixel, D: ImageDim> Image<T, D> {
+    /// Returns the format of the image
+    /// # Errors
+    /// Returns [```Error::ImageFormatNotSupported```] if the format isn't known to this crate
+    #[inline]
+    pub fn format (&self) -> Result<ImageFormat> {
+        let raw = self.get_image_info::<cl_image_format>(CL_IMAGE_FORMAT)?;
+        match ImageFormat::from_raw(raw) {
+            Some(format) => Ok(format),
+            #[cfg(feature = "error-stack")]
+            None => Err(error_stack::Report::new(Error::ImageFormatNotSupported).attach_printable(format!("unknown channel order {:#x} or channel type {:#x}", raw.image_channel_order, raw.image_channel_data_type))),
+            #[cfg(not(feature = "error-stack"))]
+            None => Err(Error::ImageFormatNotSupported)
+        }
+    }
+
+    /// Return size of each pixel of the image in bytes.
+    #[inline(always)]
+    pub fn element_size (&self) -> Result<usize> {
+        self.get_image_info(CL_IMAGE_ELEMENT_SIZE)
+    }
+
+    /// Return size in bytes of a row of the image.
+    #[inline(always)]
+    pub fn row_pitch (&self) -> Result<usize> {
+        self.get_image_info(CL_IMAGE_ROW_PITCH)
+    }
+
+    /// Return size in bytes of a 2D slice for 3D images and image arrays. For 2D images this is ```0```.
+    #[inline(always)]
+    pub fn slice_pitch (&self) -> Result<usize> {
+        self.get_image_info(CL_IMAGE_SLICE_PITCH)
+    }
+
+    /// Return width of the image in pixels.
+    #[inline(always)]
+    pub fn width (&self) -> Result<usize> {
+        self.get_image_info(CL_IMAGE_WIDTH)
+    }
+
+    /// Return height of the image in pixels.
+    #[inline(always)]
+    pub fn height (&self) -> Result<usize> {
+        self.get_image_info(CL_IMAGE_HEIGHT)
+    }
+
+    /// Return depth of the image in pixels. For 2D images and image arrays this is ```0```.
+    #[inline(always)]
+    pub fn depth (&self) -> Result<usize> {
+        self.get_image_info(CL_IMAGE_DEPTH)
+    }
+
+    /// Return the number of images in the image array. For other image types this is ```0```.
+    #[inline(always)]
+    pub fn array_size (&self) -> Result<usize> {
+        self.get_image_info(CL_IMAGE_ARRAY_SIZE)
+    }
+
+    /// Returns the region covering the whole image
+    #[inline]
+    pub fn dims (&self) -> Result<[usize; 3]> {
+        let z = match D::TYPE {
+            MemObjectType::Image3D => self.depth()?,
+            MemObjectType::Image2DArray | MemObjectType::Image1DArray => self.array_size()?,
+            _ => 1
+        };
+
+        Ok([self.width()?, self.height()?, z])
+    }
+
+    /// Returns the number of pixels of the image
+    #[inline(always)]
+    pub fn len (&self) -> Result<usize> {
+        let [x, y, z] = self.dims()?;
+        Ok(x * y * z)
+    }
+
+    /// Returns the flags argument value specified when the image is created
+    #[inline(always)]
+    pub fn flags (&self) -> Result<MemFlag> {
+        self.get_mem_info(CL_MEM_FLAGS)
+    }
+
+    /// Return context specified when the image is created.
+    #[inline(always)]
+    pub fn context (&self) -> Result<Context> {
+        let ctx : Context = self.get_mem_info(CL_MEM_CONTEXT)?;
+        unsafe { tri_panic!(clRetainContext(ctx.0)); }
+        Ok(ctx)
+    }
+
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn to_vec (&self, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Map<Vec<T>, ReadImage<'_, 'static>, impl FnOnce(()) -> Vec<T>>> {
+        self.to_vec_with_queue(CommandQueue::default(), wait)
+    }
+
+    #[inline(always)]
+    pub fn to_vec_with_queue (&self, queue: &CommandQueue, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Map<Vec<T>, ReadImage<'_, 'static>, impl FnOnce(()) -> Vec<T>>> {
+        self.read_with_queue(queue, [0; 3], self.dims()?, wait)
+    }
+
+    /// Reads the pixels inside the region into a new vector
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn read (&self, origin: [usize; 3], region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Map<Vec<T>, ReadImage<'_, 'static>, impl FnOnce(()) -> Vec<T>>> {
+        self.read_with_queue(CommandQueue::default(), origin, region, wait)
+    }
+
+    /// Reads the pixels inside the region into a new vector
+    pub fn read_with_queue (&self, queue: &CommandQueue, origin: [usize; 3], region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<Map<Vec<T>, ReadImage<'_, 'static>, impl FnOnce(()) -> Vec<T>>> {
+        let len = region.iter().try_fold(1usize, |x, y| x.checked_mul(*y)).expect("Integer overflow. Too many pixels in region");
+        let (ptr, _, len) = Vec::with_capacity(len).into_raw_parts();
+        
+        let read = unsafe { 
+            let dst = core::slice::from_raw_parts_mut::<'static, T>(ptr, len);
+            self.read_into_with_queue(queue, origin, region, dst, wait)
+        };
+
+        match read {
+            Ok(read) => Ok(read.map(move |_| unsafe { Vec::from_raw_parts(ptr, len, len) })),
+            Err(e) => {
+                let _ = unsafe { Vec::from_raw_parts(ptr, len, len) };
+                Err(e)
+            }
+        }
+    }
+
+    /// Reads the pixels inside the region into ```dst```, tightly packed
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn read_into<'a> (&self, origin: [usize; 3], region: [usize; 3], dst: &'a mut [T], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<ReadImage<'_, 'a>> {
+        self.read_into_with_queue(CommandQueue::default(), origin, region, dst, wait)
+    }
+
+    /// Reads the pixels inside the region into ```dst```, tightly packed
+    #[inline(always)]
+    pub fn read_into_with_queue<'a> (&self, queue: &CommandQueue, origin: [usize; 3], region: [usize; 3], dst: &'a mut [T], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<ReadImage<'_, 'a>> {
+        ReadImage::new(queue, false, self, origin, region, dst, wait)
+    }
+
+    /// Writes the tightly packed pixels of ```src``` into the region
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn write<'a> (&mut self, origin: [usize; 3], region: [usize; 3], src: &'a [T], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<WriteImage<'a, '_>> {
+        self.write_with_queue(CommandQueue::default(), origin, region, src, wait)
+    }
+
+    /// Writes the tightly packed pixels of ```src``` into the region
+    #[inline(always)]
+    pub fn write_with_queue<'a> (&mut self, queue: &CommandQueue, origin: [usize; 3], region: [usize; 3], src: &'a [T], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<WriteImage<'a, '_>> {
+        WriteImage::new(queue, false, src, self, origin, region, wait)
+    }
+
+    /// Copies the region at ```origin``` into ```dst_origin``` inside ```dst```
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn copy_to<'a, E: ImageDim> (&self, origin: [usize; 3], dst: &'a mut Image<T, E>, dst_origin: [usize; 3], region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<CopyImage<'_, 'a>> {
+        self.copy_to_with_queue(CommandQueue::default(), origin, dst, dst_origin, region, wait)
+    }
+
+    /// Copies the region at ```origin``` into ```dst_origin``` inside ```dst```
+    #[inline(always)]
+    pub fn copy_to_with_queue<'a, E: ImageDim> (&self, queue: &CommandQueue, origin: [usize; 3], dst: &'a mut Image<T, E>, dst_origin: [usize; 3], region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<CopyImage<'_, 'a>> {
+        CopyImage::new(queue, self, origin, dst, dst_origin, region, wait)
+    }
+
+    /// Copies the region at ```origin``` into ```dst```, tightly packed and starting at element ```offset```
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn copy_to_buffer<'a> (&self, origin: [usize; 3], region: [usize; 3], dst: &'a mut MemBuffer<T>, offset: usize, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<CopyImageToBuffer<'_, 'a>> {
+        self.copy_to_buffer_with_queue(CommandQueue::default(), origin, region, dst, offset, wait)
+    }
+
+    /// Copies the region at ```origin``` into ```dst```, tightly packed and starting at element ```offset```
+    #[inline(always)]
+    pub fn copy_to_buffer_with_queue<'a> (&self, queue: &CommandQueue, origin: [usize; 3], region: [usize; 3], dst: &'a mut MemBuffer<T>, offset: usize, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<CopyImageToBuffer<'_, 'a>> {
+        CopyImageToBuffer::new(queue, self, origin, region, dst, offset, wait)
+    }
+
+    /// Copies the tightly packed pixels of ```src```, starting at element ```offset```, into the region at ```origin```
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn copy_from_buffer<'a> (&mut self, src: &'a MemBuffer<T>, offset: usize, origin: [usize; 3], region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<CopyBufferToImage<'a, '_>> {
+        self.copy_from_buffer_with_queue(CommandQueue::default(), src, offset, origin, region, wait)
+    }
+
+    /// Copies the tightly packed pixels of ```src```, starting at element ```offset```, into the region at ```origin```
+    #[inline(always)]
+    pub fn copy_from_buffer_with_queue<'a> (&mut self, queue: &CommandQueue, src: &'a MemBuffer<T>, offset: usize, origin: [usize; 3], region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<CopyBufferToImage<'a, '_>> {
+        CopyBufferToImage::new(queue, src, offset, self, origin, region, wait)
+    }
+
+    /// Sets every pixel inside the region to ```color```
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn fill (&mut self, color: T::Color, origin: [usize; 3], region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<FillImage<'_>> {
+        self.fill_with_queue(CommandQueue::default(), color, origin, region, wait)
+    }
+
+    /// Sets every pixel inside the region to ```color```
+    #[inline(always)]
+    pub fn fill_with_queue (&mut self, queue: &CommandQueue, color: T::Color, origin: [usize; 3], region: [usize; 3], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<FillImage<'_>> {
+        FillImage::new(queue, color, self, origin, region, wait)
+    }
+
+    fn create_from_slice (ctx: &Context, flags: MemFlag, src: &[T], dims: [usize; 3]) -> Result<Self> {
+        let len = dims.iter().try_fold(1usize, |x, y| x.checked_mul(*y)).expect("Integer overflow. Too many pixels in image");
+        if src.len() != len {
+            #[cfg(feature = "error-stack")]
+            return Err(error_stack::Report::new(Error::InvalidValue).attach_printable(format!("expected {len} pixels, found {}", src.len())));
+            #[cfg(not(feature = "error-stack"))]
+            return Err(Error::InvalidValue);
+        }
+
+        let flags = flags | MemFlag::COPY_HOST_PTR;
+        unsafe { Self::create(ctx, flags, dims, NonNull::new(src.as_ptr() as *mut _)) }
+    }
+
+    /// # Safety
+    /// ```host_ptr``` must point to enough pixels to fill the image, or be ```None```
+    unsafe fn create (ctx: &Context, flags: MemFlag, dims: [usize; 3], host_ptr: Option<NonNull<T>>) -> Result<Self> {
+        let host_ptr = match host_ptr {
+            Some(x) => x.as_ptr().cast(),
+            None => core::ptr::null_mut()
+        };
+
+        let (depth, array_size) = match D::TYPE {
+            MemObjectType::Image3D => (dims[2], 0),
+            MemObjectType::Image2DArray | MemObjectType::Image1DArray => (0, dims[2]),
+            _ => (0, 0)
+        };
+
+        let format = T::FORMAT.to_raw();
+        let desc = cl_image_desc {
+            image_type: D::TYPE as u32,
+            image_width: dims[0],
+            image_height: dims[1],
+            image_depth: depth,
+            image_array_size: array_size,
+            image_row_pitch: 0,
+            image_slice_pitch: 0,
+            num_mip_levels: 0,
+            num_samples: 0,
+            buffer: core::ptr::null_mut()
+        };
+
+        let mut err = 0;
+        let id = clCreateImage(ctx.0, flags.bits(), &format, &desc, host_ptr, &mut err);
+
+        if err == 0 {
+            return Ok(Self(id, PhantomData));
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "error-stack")] {
+                let err = Error::from(err);
+                let report = error_stack::Report::new(err);
+
+                let report = match err {
+                    Error::InvalidContext => report.attach_printable(format!("'{:?}' is not a valid context", ctx.0)),
+                    Error::InvalidValue => report.attach_printable(format!("'{:?}' is not a valid flag", flags)),
+                    Error::InvalidImageFormatDescriptor => report.attach_printable(format!("'{:?}' is not a valid image format", T::FORMAT)),
+                    Error::InvalidImageDescriptor => report.attach_printable(format!("'{:?}' is not a valid image descriptor", desc)),
+                    Error::InvalidImageSize => report.attach_printable(format!("image dimensions {dims:?} exceed the maximum values supported by the devices in the context")),
+                    Error::InvalidHostPtr => report.attach_printable(format!("'{:?}' is not a valid host pointer", host_ptr)),
+                    Error::ImageFormatNotSupported => report.attach_printable(format!("'{:?}' is not supported by the context", T::FORMAT)),
+                    Error::InvalidOperation => report.attach_printable("there are no devices in the context that support images"),
+                    Error::MemObjectAllocationFailure => report.attach_printable("failed to allocate memory object"),
+                    Error::OutOfResources => report.attach_printable("failure to allocate resources required by the OpenCL implementation on the device"),
+                    Error::OutOfHostMemory => report.attach_printable("failure to allocate resources required by the OpenCL implementation on the host"),
+                    _ => report
+                };
+
+                Err(report)
+            } else {
+                Err(Error::from(err))
+            }
+        }
+    }
+
+    #[inline]
+    fn get_image_info<O> (&self, ty: cl_image_info) -> Result<O> {
+        let mut result = MaybeUninit::<O>::uninit();
+
+        unsafe {
+            let err = clGetImageInfo(self.0, ty, core::mem::size_of::<O>(), result.as_mut_ptr().cast(), core::ptr::null_mut());
+            if err == 0 {
+                return Ok(result.assume_init());
+            }
+
+            cfg_if::cfg_if! {
+                if #[cfg(feature = "error-stack")] {
+                    let err = Error::from(err);
+                    let report = error_stack::Report::new(err);
+    
+                    let report = match err {
+                        Error::InvalidMemObject => report.attach_printable(format!("'{:?}' is not a valid image object", self.0)),
+                        Error::InvalidValue => report.attach_printable(format!("'{ty}' is not one of the supported values or size in bytes specified by param_value_size is less than size of return type and param_value is not a NULL value")),
+                        _ => report
+                    };
+    
+                    Err(report)
+                } else {
+                    Err(Error::from(err))
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn get_mem_info<O> (&self, ty: cl_mem_info) -> Result<O> {
+        let mut result = MaybeUninit::<O>::uninit();
+
+        unsafe {
+            let err = clGetMemObjectInfo(self.0, ty, core::mem::size_of::<O>(), result.as_mut_ptr().cast(), core::ptr::null_mut());
+            if err == 0 {
+                return Ok(result.assume_init());
+            }
+
+            cfg_if::cfg_if! {
+                if #[cfg(feature = "error-stack")] {
+                    let err = Error::from(err);
+                    let report = error_stack::Report::new(err);
+    
+                    let report = match err {
+                        Error::InvalidMemObject => report.attach_printable(format!("'{:?}' is not a valid memory object", self.0)),
+                        Error::InvalidValue => report.attach_printable(format!("'{ty}' is not one of the supported values or size in bytes specified by param_value_size is less than size of return type and param_value is not a NULL value")),
+                        _ => report
+                    };
+    
+                    Err(report)
+                } else {
+                    Err(Error::from(err))
+                }
+            }
+        }
+    }
+}
+
+unsafe impl<T: ImagePixel, D: ImageDim> MemObject for Image<T, D> {
+    #[inline(always)]
+    fn id (&self) -> cl_mem {
+        self.0
+    }
+}
+
+impl<T: ImagePixel, D: ImageDim> Debug for Image<T, D> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Image")
+            .field("id", &self.0)
+            .field("type", &D::TYPE)
+            .field("format", &self.format())
+            .field("dims", &self.dims())
+            .finish()
+    }
+}
+
+impl<T: ImagePixel, D: ImageDim> Drop for Image<T, D> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        unsafe {
+            tri_panic!(clReleaseMemObject(self.0));
+        }
+    }
+}
+
+unsafe impl<T: ImagePixel + Send, D: ImageDim> Send for Image<T, D> {}
+unsafe impl<T: ImagePixel + Sync, D: ImageDim> Sync for Image<T, D> {}
@@ -0,0 +1,140 @@
+use opencl_sys::{cl_image_format, cl_channel_order, cl_channel_type, CL_R, CL_A, CL_RG, CL_RA, CL_RGB, CL_RGBA, CL_BGRA, CL_ARGB, CL_INTENSITY, CL_LUMINANCE, CL_Rx, CL_RGx, CL_RGBx, CL_DEPTH, CL_SNORM_INT8, CL_SNORM_INT16, CL_UNORM_INT8, CL_UNORM_INT16, CL_UNORM_SHORT_565, CL_UNORM_SHORT_555, CL_UNORM_INT_101010, CL_SIGNED_INT8, CL_SIGNED_INT16, CL_SIGNED_INT32, CL_UNSIGNED_INT8, CL_UNSIGNED_INT16, CL_UNSIGNED_INT32, CL_HALF_FLOAT, CL_FLOAT};
+
+macro_rules! raw_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident : $raw:ty {
+            $(
+                $(#[$vmeta:meta])*
+                $variant:ident = $value:ident
+            ),+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[repr(u32)]
+        pub enum $name {
+            $(
+                $(#[$vmeta])*
+                $variant = $value
+            ),+
+        }
+
+        impl $name {
+            /// Returns the variant with the specified OpenCL value, if any.
+            #[allow(non_upper_case_globals)]
+            #[inline]
+            pub const fn from_raw (v: $raw) -> Option<Self> {
+                match v {
+                    $(
+                        $value => Some(Self::$variant),
+                    )+
+                    _ => None
+                }
+            }
+        }
+    };
+}
+
+raw_enum! {
+    /// Number of channels and the channel layout of an image
+    pub enum ChannelOrder : cl_channel_order {
+        R = CL_R,
+        A = CL_A,
+        RG = CL_RG,
+        RA = CL_RA,
+        /// Only valid with [```ChannelType::UnormShort565```], [```ChannelType::UnormShort555```] and [```ChannelType::UnormInt101010```]
+        RGB = CL_RGB,
+        RGBA = CL_RGBA,
+        BGRA = CL_BGRA,
+        ARGB = CL_ARGB,
+        Intensity = CL_INTENSITY,
+        Luminance = CL_LUMINANCE,
+        Rx = CL_Rx,
+        RGx = CL_RGx,
+        RGBx = CL_RGBx,
+        Depth = CL_DEPTH
+    }
+}
+
+raw_enum! {
+    /// Size and interpretation of each channel of an image
+    pub enum ChannelType : cl_channel_type {
+        SnormInt8 = CL_SNORM_INT8,
+        SnormInt16 = CL_SNORM_INT16,
+        UnormInt8 = CL_UNORM_INT8,
+        UnormInt16 = CL_UNORM_INT16,
+        UnormShort565 = CL_UNORM_SHORT_565,
+        UnormShort555 = CL_UNORM_SHORT_555,
+        UnormInt101010 = CL_UNORM_INT_101010,
+        SignedInt8 = CL_SIGNED_INT8,
+        SignedInt16 = CL_SIGNED_INT16,
+        SignedInt32 = CL_SIGNED_INT32,
+        UnsignedInt8 = CL_UNSIGNED_INT8,
+        UnsignedInt16 = CL_UNSIGNED_INT16,
+        UnsignedInt32 = CL_UNSIGNED_INT32,
+        HalfFloat = CL_HALF_FLOAT,
+        Float = CL_FLOAT
+    }
+}
+
+impl ChannelOrder {
+    /// Number of channels of each pixel. Packed orders (i.e. ```RGB```) count as a single channel. 
+    #[inline]
+    pub const fn channels (&self) -> usize {
+        match self {
+            Self::R | Self::A | Self::Rx | Self::Intensity | Self::Luminance | Self::Depth | Self::RGB | Self::RGBx => 1,
+            Self::RG | Self::RA | Self::RGx => 2,
+            Self::RGBA | Self::BGRA | Self::ARGB => 4
+        }
+    }
+}
+
+impl ChannelType {
+    /// Size, in bytes, of each channel. Packed types (i.e. ```UnormShort565```) return the size of the whole pixel.
+    #[inline]
+    pub const fn size (&self) -> usize {
+        match self {
+            Self::SnormInt8 | Self::UnormInt8 | Self::SignedInt8 | Self::UnsignedInt8 => 1,
+            Self::SnormInt16 | Self::UnormInt16 | Self::SignedInt16 | Self::UnsignedInt16 | Self::HalfFloat | Self::UnormShort565 | Self::UnormShort555 => 2,
+            Self::SignedInt32 | Self::UnsignedInt32 | Self::Float | Self::UnormInt101010 => 4
+        }
+    }
+}
+
+/// Image format descriptor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ImageFormat {
+    pub order: ChannelOrder,
+    pub ty: ChannelType
+}
+
+impl ImageFormat {
+    #[inline(always)]
+    pub const fn new (order: ChannelOrder, ty: ChannelType) -> Self {
+        Self { order, ty }
+    }
+
+    /// Size, in bytes, of each pixel
+    #[inline(always)]
+    pub const fn pixel_size (&self) -> usize {
+        self.order.channels() * self.ty.size()
+    }
+
+    /// Returns the format described by ```raw```, or ```None``` if it's not known to this crate.
+    #[inline]
+    pub const fn from_raw (raw: cl_image_format) -> Option<Self> {
+        match (ChannelOrder::from_raw(raw.image_channel_order), ChannelType::from_raw(raw.image_channel_data_type)) {
+            (Some(order), Some(ty)) => Some(Self::new(order, ty)),
+            _ => None
+        }
+    }
+
+    #[inline(always)]
+    pub const fn to_raw (&self) -> cl_image_format {
+        cl_image_format {
+            image_channel_order: self.order as cl_channel_order,
+            image_channel_data_type: self.ty as cl_channel_type
+        }
+    }
+}
@@ -0,0 +1 @@
+flat_mod!(format, pixel, base);
\ No newline at end of file
@@ -0,0 +1,58 @@
+use crate::buffer::DeviceElement;
+use super::{ImageFormat, ChannelOrder, ChannelType};
+
+/// Rust type that can be stored as the pixel of an image
+/// # Safety
+/// The size and memory layout of ```Self``` must match that of a pixel with format ```FORMAT```
+pub unsafe trait ImagePixel: DeviceElement {
+    const FORMAT: ImageFormat;
+    /// Color used to fill images of this pixel type. ```[f32; 4]``` for floating-point and normalized pixels, ```[i32; 4]``` for signed integer pixels and ```[u32; 4]``` for unsigned integer pixels.
+    type Color: 'static + Copy;
+}
+
+/// Wrapper that marks integer channels as normalized. Normalized channels are read as floating-point numbers by kernels, in the range ```[0, 1]``` for unsigned types and ```[-1, 1]``` for signed types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(transparent)]
+pub struct Norm<T> (pub T);
+
+unsafe impl<T: DeviceElement> DeviceElement for Norm<T> {}
+
+macro_rules! impl_pixel {
+    ($($ty:ty => $channel:ident as $color:ty),+) => {
+        $(
+            unsafe impl ImagePixel for $ty {
+                const FORMAT: ImageFormat = ImageFormat::new(ChannelOrder::R, ChannelType::$channel);
+                type Color = $color;
+            }
+
+            unsafe impl ImagePixel for [$ty; 2] {
+                const FORMAT: ImageFormat = ImageFormat::new(ChannelOrder::RG, ChannelType::$channel);
+                type Color = $color;
+            }
+
+            unsafe impl ImagePixel for [$ty; 4] {
+                const FORMAT: ImageFormat = ImageFormat::new(ChannelOrder::RGBA, ChannelType::$channel);
+                type Color = $color;
+            }
+        )+
+    };
+}
+
+impl_pixel! {
+    u8 => UnsignedInt8 as [u32; 4],
+    u16 => UnsignedInt16 as [u32; 4],
+    u32 => UnsignedInt32 as [u32; 4],
+    i8 => SignedInt8 as [i32; 4],
+    i16 => SignedInt16 as [i32; 4],
+    i32 => SignedInt32 as [i32; 4],
+    f32 => Float as [f32; 4],
+    Norm<u8> => UnormInt8 as [f32; 4],
+    Norm<u16> => UnormInt16 as [f32; 4],
+    Norm<i8> => SnormInt8 as [f32; 4],
+    Norm<i16> => SnormInt16 as [f32; 4]
+}
+
+unsafe impl ImagePixel for Norm<[u8; 4]> {
+    const FORMAT: ImageFormat = ImageFormat::new(ChannelOrder::RGBA, ChannelType::UnormInt8);
+    type Color = [f32; 4];
+}
@@ -47,7 +47,7 @@ impl<'a> Builder<'a> {
         self
     }
 
-    fn set_mem_arg<T: Copy + Unpin> (&mut self, idx: u32, v: &MemBuffer<T>) -> &mut Self {
+    fn set_mem_arg<T: DeviceElement> (&mut self, idx: u32, v: &MemBuffer<T>) -> &mut Self {
         self
     }
 
@@ -5,14 +5,21 @@ flat_mod!(flags, builder);
 
 use core::{mem::MaybeUninit, ptr::addr_of};
 use alloc::{string::{String}, vec::Vec};
-use opencl_sys::{cl_kernel, clReleaseKernel, clCreateKernel, clGetKernelInfo, cl_kernel_info, CL_KERNEL_FUNCTION_NAME, CL_KERNEL_NUM_ARGS, CL_KERNEL_REFERENCE_COUNT, CL_KERNEL_CONTEXT, CL_KERNEL_PROGRAM, clSetKernelArg, cl_kernel_arg_info, CL_KERNEL_ARG_ADDRESS_QUALIFIER, CL_KERNEL_ARG_ACCESS_QUALIFIER, clGetKernelArgInfo, CL_KERNEL_ARG_NAME, CL_KERNEL_ARG_TYPE_NAME, CL_KERNEL_ARG_TYPE_QUALIFIER, clEnqueueNDRangeKernel, cl_mem, clRetainContext, clRetainProgram};
+use opencl_sys::{cl_kernel, clReleaseKernel, clCreateKernel, clGetKernelInfo, cl_kernel_info, CL_KERNEL_FUNCTION_NAME, CL_KERNEL_NUM_ARGS, CL_KERNEL_REFERENCE_COUNT, CL_KERNEL_CONTEXT, CL_KERNEL_PROGRAM, clSetKernelArg, cl_kernel_arg_info, CL_KERNEL_ARG_ADDRESS_QUALIFIER, CL_KERNEL_ARG_ACCESS_QUALIFIER, clGetKernelArgInfo, CL_KERNEL_ARG_NAME, CL_KERNEL_ARG_TYPE_NAME, CL_KERNEL_ARG_TYPE_QUALIFIER, clEnqueueNDRangeKernel, cl_mem, cl_sampler, clRetainContext, clRetainProgram, clRetainMemObject, clReleaseMemObject, clRetainSampler, clReleaseSampler};
 use parking_lot::{RawMutex};
-use crate::{prelude::{Error, Program, Context, CommandQueue, BaseEvent}, error::Result, buffer::MemBuffer};
+use crate::{prelude::{Error, Program, Context, CommandQueue, BaseEvent}, error::Result, buffer::{MemBuffer, DeviceElement}, image::{Image, ImagePixel, ImageDim}, sampler::Sampler};
 
 #[cfg(feature = "error-stack")]
 use alloc::format;
 
-pub struct Kernel (pub(crate) cl_kernel, pub(super) RawMutex);
+pub struct Kernel (pub(crate) cl_kernel, pub(super) RawMutex, Vec<ArgObject>);
+
+/// OpenCL object retained by a kernel for as long as it's set as one of its arguments
+enum ArgObject {
+    None,
+    Mem(cl_mem),
+    Sampler(cl_sampler)
+}
 
 impl Kernel {
     /// Creates a new kernel from a program and a name.
@@ -25,7 +32,7 @@ impl Kernel {
         
         let mut err = 0;
         let id = clCreateKernel(program.0, name.as_ptr().cast(), &mut err);
-        if err == 0 { return Ok(Self(id, parking_lot::lock_api::RawMutex::INIT)); }
+        if err == 0 { return Ok(Self(id, parking_lot::lock_api::RawMutex::INIT, Vec::new())); }
 
         cfg_if::cfg_if! {
             if #[cfg(feature = "error-stack")] {
@@ -49,23 +56,74 @@ impl Kernel {
         }
     }
 
+    /// Sets the value of the argument at ```idx```.
     #[inline(always)]
-    fn set_arg<T: Copy> (&mut self, idx: u32, v: T) -> Result<()> {
+    pub fn set_arg<T: Copy> (&mut self, idx: u32, v: T) -> Result<()> {
         let err = unsafe { clSetKernelArg(self.0, idx, core::mem::size_of::<T>(), addr_of!(v).cast()) };
-        self.parse_error_set_arg(err, idx, core::mem::size_of::<T>())
+        self.parse_error_set_arg(err, idx, core::mem::size_of::<T>())?;
+        self.hold_arg(idx, ArgObject::None);
+        Ok(())
+    }
+
+    /// Sets a memory buffer as the argument at ```idx```. Sub-buffers (i.e. [```ReadSlice```](crate::buffer::ReadSlice) and [```WriteSlice```](crate::buffer::WriteSlice)) can be passed by dereferencing them.
+    /// The buffer is retained by the kernel until the argument is replaced or the kernel is dropped.
+    #[inline(always)]
+    pub fn set_mem_arg<T: DeviceElement> (&mut self, idx: u32, v: &MemBuffer<T>) -> Result<()> {
+        self.set_mem_object_arg(idx, v.0)
+    }
+
+    /// Sets an image as the argument at ```idx```. The image is retained by the kernel until the argument is replaced or the kernel is dropped.
+    #[inline(always)]
+    pub fn set_image_arg<T: ImagePixel, D: ImageDim> (&mut self, idx: u32, v: &Image<T, D>) -> Result<()> {
+        self.set_mem_object_arg(idx, v.0)
     }
 
+    /// Sets a sampler as the argument at ```idx```. The sampler is retained by the kernel until the argument is replaced or the kernel is dropped.
     #[inline(always)]
-    fn set_mem_arg<T: Copy + Unpin> (&mut self, idx: u32, v: &MemBuffer<T>) -> Result<()> {
-        let err = unsafe { clSetKernelArg(self.0, idx, core::mem::size_of::<cl_mem>(), addr_of!(v.0).cast()) };
-        self.parse_error_set_arg(err, idx, core::mem::size_of::<cl_mem>())
+    pub fn set_sampler_arg (&mut self, idx: u32, v: &Sampler) -> Result<()> {
+        let err = unsafe { clSetKernelArg(self.0, idx, core::mem::size_of::<cl_sampler>(), addr_of!(v.0).cast()) };
+        self.parse_error_set_arg(err, idx, core::mem::size_of::<cl_sampler>())?;
+
+        unsafe { tri_panic!(clRetainSampler(v.0)) }
+        self.hold_arg(idx, ArgObject::Sampler(v.0));
+        Ok(())
     }
 
+    /// Sets a pipe as the argument at ```idx```. The pipe is retained by the kernel until the argument is replaced or the kernel is dropped.
+    #[cfg(feature = "cl2")]
     #[inline(always)]
-    fn alloc_arg<T> (&mut self, idx: u32, len: usize) -> Result<()> {
+    pub fn set_pipe_arg<T: DeviceElement> (&mut self, idx: u32, v: &crate::pipe::Pipe<T>) -> Result<()> {
+        self.set_mem_object_arg(idx, v.0)
+    }
+
+    /// Allocates ```len``` elements of local memory for the argument at ```idx```.
+    #[inline(always)]
+    pub fn alloc_arg<T> (&mut self, idx: u32, len: usize) -> Result<()> {
         let arg_size = len.checked_mul(core::mem::size_of::<T>()).expect("Kernel argument size overflow");
         let err = unsafe { clSetKernelArg(self.0, idx, arg_size, core::ptr::null_mut()) };
-        self.parse_error_set_arg(err, idx, arg_size)
+        self.parse_error_set_arg(err, idx, arg_size)?;
+        self.hold_arg(idx, ArgObject::None);
+        Ok(())
+    }
+
+    fn set_mem_object_arg (&mut self, idx: u32, id: cl_mem) -> Result<()> {
+        let err = unsafe { clSetKernelArg(self.0, idx, core::mem::size_of::<cl_mem>(), addr_of!(id).cast()) };
+        self.parse_error_set_arg(err, idx, core::mem::size_of::<cl_mem>())?;
+
+        unsafe { tri_panic!(clRetainMemObject(id)) }
+        self.hold_arg(idx, ArgObject::Mem(id));
+        Ok(())
+    }
+
+    /// Keeps ```obj``` alive while it's the argument at ```idx```, releasing the object previously set there (if any)
+    fn hold_arg (&mut self, idx: u32, obj: ArgObject) {
+        let idx = idx as usize;
+        if idx >= self.2.len() {
+            if let ArgObject::None = obj { return }
+            self.2.resize_with(idx + 1, || ArgObject::None);
+        }
+
+        self.2[idx] = obj;
     }
 
     /// Return the kernel function name.
@@ -342,5 +400,18 @@ impl Drop for Kernel {
     }
 }
 
+impl Drop for ArgObject {
+    #[inline(always)]
+    fn drop(&mut self) {
+        unsafe {
+            match *self {
+                Self::None => {},
+                Self::Mem(id) => tri_panic!(clReleaseMemObject(id)),
+                Self::Sampler(id) => tri_panic!(clReleaseSampler(id))
+            }
+        }
+    }
+}
+
 unsafe impl Send for Kernel {}
 unsafe impl Sync for Kernel {}
\ No newline at end of file
@@ -41,7 +41,7 @@ pub mod prelude {
     pub use crate::error::{Result, Error};
     pub use crate::program::Program;
     pub use crate::event::{Event, BaseEvent, EMPTY};
-    pub use crate::buffer::{MemBuffer};
+    pub use crate::buffer::{MemBuffer, DeviceElement};
     pub use crate::kernel::Kernel;
 }
 
@@ -53,10 +53,18 @@ pub mod queue;
 pub mod context;
 pub mod program;
 pub mod buffer;
+/// OpenCL image objects
+pub mod image;
+/// OpenCL sampler objects
+pub mod sampler;
 pub mod event;
 pub mod kernel;
 pub mod utils;
 
 #[cfg(feature = "cl2")]
 pub mod svm;
-//pub mod vec;
+/// OpenCL 2.0 pipe objects
+#[cfg(feature = "cl2")]
+pub mod pipe;
+/// Growable OpenCL buffers
+pub mod vec;
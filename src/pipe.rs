@@ -0,0 +1,174 @@
+use core::{marker::PhantomData, mem::MaybeUninit, fmt::Debug};
+use opencl_sys::{cl_mem, cl_pipe_info, cl_mem_info, clCreatePipe, clGetPipeInfo, clGetMemObjectInfo, clReleaseMemObject, clRetainContext, CL_PIPE_PACKET_SIZE, CL_PIPE_MAX_PACKETS, CL_MEM_CONTEXT, CL_MEM_FLAGS};
+use crate::{prelude::{Result, Error, Context}, buffer::{MemFlag, MemObject, DeviceElement}};
+
+#[cfg(feature = "error-stack")]
+use alloc::format;
+
+/// OpenCL pipe. A FIFO of packets of type ```T``` that can only be accessed by kernels.
+pub struct Pipe<T: DeviceElement> (pub(crate) cl_mem, PhantomData<T>);
+
+impl<T: DeviceElement> Pipe<T> {
+    /// Creates a new pipe that can hold up to ```max_packets``` packets of type ```T```.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn new (max_packets: u32, flags: MemFlag) -> Result<Self> {
+        Self::with_context(Context::default(), max_packets, flags)
+    }
+
+    /// Creates a new pipe that can hold up to ```max_packets``` packets of type ```T```.
+    /// # Errors
+    /// Returns [```Error::InvalidPipeSize```] if ```T``` is zero-sized or its size doesn't fit in a ```u32```.
+    pub fn with_context (ctx: &Context, max_packets: u32, flags: MemFlag) -> Result<Self> {
+        let packet_size = match u32::try_from(core::mem::size_of::<T>()) {
+            Ok(0) | Err(_) => {
+                #[cfg(feature = "error-stack")]
+                return Err(error_stack::Report::new(Error::InvalidPipeSize).attach_printable(format!("packet size of {} bytes is not valid", core::mem::size_of::<T>())));
+                #[cfg(not(feature = "error-stack"))]
+                return Err(Error::InvalidPipeSize);
+            },
+            Ok(x) => x
+        };
+
+        let mut err = 0;
+        let id = unsafe { clCreatePipe(ctx.0, flags.bits(), packet_size, max_packets, core::ptr::null(), &mut err) };
+
+        if err == 0 {
+            return Ok(Self(id, PhantomData));
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "error-stack")] {
+                let err = Error::from(err);
+                let report = error_stack::Report::new(err);
+
+                let report = match err {
+                    Error::InvalidContext => report.attach_printable(format!("'{:?}' is not a valid context", ctx.0)),
+                    Error::InvalidValue => report.attach_printable(format!("'{:?}' is not a valid flag. Pipes only accept MemFlag::READ_WRITE", flags)),
+                    Error::InvalidPipeSize => report.attach_printable(format!("packet size ({packet_size} bytes) exceeds the device's maximum pipe packet size, or max packets ({max_packets}) is zero")),
+                    Error::MemObjectAllocationFailure => report.attach_printable("failed to allocate memory object"),
+                    Error::OutOfResources => report.attach_printable("failure to allocate resources required by the OpenCL implementation on the device"),
+                    Error::OutOfHostMemory => report.attach_printable("failure to allocate resources required by the OpenCL implementation on the host"),
+                    _ => report
+                };
+
+                Err(report)
+            } else {
+                Err(Error::from(err))
+            }
+        }
+    }
+
+    /// Return pipe packet size specified when the pipe is created, in bytes.
+    #[inline(always)]
+    pub fn packet_size (&self) -> Result<u32> {
+        self.get_info(CL_PIPE_PACKET_SIZE)
+    }
+
+    /// Return max. number of packets specified when the pipe is created.
+    #[inline(always)]
+    pub fn max_packets (&self) -> Result<u32> {
+        self.get_info(CL_PIPE_MAX_PACKETS)
+    }
+
+    /// Returns the flags argument value specified when the pipe is created
+    #[inline(always)]
+    pub fn flags (&self) -> Result<MemFlag> {
+        self.get_mem_info(CL_MEM_FLAGS)
+    }
+
+    /// Return context specified when the pipe is created.
+    #[inline(always)]
+    pub fn context (&self) -> Result<Context> {
+        let ctx : Context = self.get_mem_info(CL_MEM_CONTEXT)?;
+        unsafe { tri_panic!(clRetainContext(ctx.0)); }
+        Ok(ctx)
+    }
+
+    #[inline]
+    fn get_info<O> (&self, ty: cl_pipe_info) -> Result<O> {
+        let mut result = MaybeUninit::<O>::uninit();
+
+        unsafe {
+            let err = clGetPipeInfo(self.0, ty, core::mem::size_of::<O>(), result.as_mut_ptr().cast(), core::ptr::null_mut());
+            if err == 0 {
+                return Ok(result.assume_init());
+            }
+
+            cfg_if::cfg_if! {
+                if #[cfg(feature = "error-stack")] {
+                    let err = Error::from(err);
+                    let report = error_stack::Report::new(err);
+    
+                    let report = match err {
+                        Error::InvalidMemObject => report.attach_printable(format!("'{:?}' is not a valid pipe object", self.0)),
+                        Error::InvalidValue => report.attach_printable(format!("'{ty}' is not one of the supported values or size in bytes specified by param_value_size is less than size of return type and param_value is not a NULL value")),
+                        _ => report
+                    };
+    
+                    Err(report)
+                } else {
+                    Err(Error::from(err))
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn get_mem_info<O> (&self, ty: cl_mem_info) -> Result<O> {
+        let mut result = MaybeUninit::<O>::uninit();
+
+        unsafe {
+            let err = clGetMemObjectInfo(self.0, ty, core::mem::size_of::<O>(), result.as_mut_ptr().cast(), core::ptr::null_mut());
+            if err == 0 {
+                return Ok(result.assume_init());
+            }
+
+            cfg_if::cfg_if! {
+                if #[cfg(feature = "error-stack")] {
+                    let err = Error::from(err);
+                    let report = error_stack::Report::new(err);
+    
+                    let report = match err {
+                        Error::InvalidMemObject => report.attach_printable(format!("'{:?}' is not a valid memory object", self.0)),
+                        Error::InvalidValue => report.attach_printable(format!("'{ty}' is not one of the supported values or size in bytes specified by param_value_size is less than size of return type and param_value is not a NULL value")),
+                        _ => report
+                    };
+    
+                    Err(report)
+                } else {
+                    Err(Error::from(err))
+                }
+            }
+        }
+    }
+}
+
+unsafe impl<T: DeviceElement> MemObject for Pipe<T> {
+    #[inline(always)]
+    fn id (&self) -> cl_mem {
+        self.0
+    }
+}
+
+impl<T: DeviceElement> Debug for Pipe<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Pipe")
+        .field("id", &self.0)
+        .field("packet_size", &self.packet_size())
+        .field("max_packets", &self.max_packets())
+        .finish()
+    }
+}
+
+impl<T: DeviceElement> Drop for Pipe<T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        unsafe {
+            tri_panic!(clReleaseMemObject(self.0));
+        }
+    }
+}
+
+unsafe impl<T: Send + DeviceElement> Send for Pipe<T> {}
+unsafe impl<T: Sync + DeviceElement> Sync for Pipe<T> {}
\ No newline at end of file
@@ -1,6 +1,10 @@
 use core::mem::MaybeUninit;
-use opencl_sys::{cl_command_queue_properties, CL_QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE, CL_QUEUE_PROFILING_ENABLE, cl_command_queue, clRetainCommandQueue, clReleaseCommandQueue, cl_command_queue_info, clGetCommandQueueInfo, CL_QUEUE_CONTEXT, CL_QUEUE_DEVICE, CL_QUEUE_PROPERTIES, clRetainContext};
-use crate::{prelude::{Context, Error, Device}, utils::ContextManager};
+use opencl_sys::{cl_command_queue_properties, CL_QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE, CL_QUEUE_PROFILING_ENABLE, cl_command_queue, clRetainCommandQueue, clReleaseCommandQueue, cl_command_queue_info, clGetCommandQueueInfo, clEnqueueMigrateMemObjects, clEnqueueMarkerWithWaitList, CL_QUEUE_CONTEXT, CL_QUEUE_DEVICE, CL_QUEUE_PROPERTIES, clRetainContext};
+use alloc::vec::Vec;
+use crate::{prelude::{Context, Error, Device, BaseEvent}, utils::ContextManager, buffer::{MemObject, MemMigrationFlag}};
+
+#[cfg(feature = "error-stack")]
+use alloc::format;
 
 /// OpenCL command queue
 #[derive(PartialEq, Eq, Hash)]
@@ -60,6 +64,86 @@ impl CommandQueue {
         ContextManager::default().queue()
     }
 
+    /// Migrates ```objects``` to the device associated with this command-queue (or to the host, if [```MemMigrationFlag::HOST```] is set).
+    pub fn migrate_all (&self, objects: &[&dyn MemObject], flags: MemMigrationFlag, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> crate::prelude::Result<BaseEvent> {
+        let objects = objects.iter().map(|x| x.id()).collect::<Vec<_>>();
+        let objects_len = u32::try_from(objects.len()).expect("Too many memory objects");
+
+        let wait = wait.into_iter().map(|x| x.as_ref().0).collect::<Vec<_>>();
+        let wait_len = u32::try_from(wait.len()).unwrap();
+        let wait = match wait_len {
+            0 => core::ptr::null(),
+            _ => wait.as_ptr()
+        };
+
+        let mut event = core::ptr::null_mut();
+        let err = unsafe { clEnqueueMigrateMemObjects(self.0, objects_len, objects.as_ptr(), flags.bits(), wait_len, wait, &mut event) };
+
+        if err == 0 {
+            return BaseEvent::new(event);
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "error-stack")] {
+                let err = Error::from(err);
+                let report = error_stack::Report::new(err);
+
+                let report = match err {
+                    Error::InvalidCommandQueue => report.attach_printable(format!("'{:?}' is not a valid command-queue", self.0)),
+                    Error::InvalidContext => report.attach_printable("the context associated with the command queue and memory objects are not the same or the context associated with command queue and events in the event wait list are not the same"),
+                    Error::InvalidMemObject => report.attach_printable("any of the memory objects is not a valid memory object"),
+                    Error::InvalidValue => report.attach_printable(format!("no memory objects were specified or '{flags:?}' is not a valid flag")),
+                    Error::InvalidEventWaitList => report.attach_printable("event objects in the event wait list are not valid events"),
+                    Error::MemObjectAllocationFailure => report.attach_printable("there is a failure to allocate memory for the specified set of memory objects"),
+                    Error::OutOfResources => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the device"),
+                    Error::OutOfHostMemory => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the host"),
+                    _ => report
+                };
+
+                Err(report)
+            } else {
+                Err(Error::from(err))
+            }
+        }
+    }
+
+    /// Enqueues a marker command, which completes once every event in ```wait``` has completed. If ```wait``` is empty, the marker completes once every previously enqueued command has completed.
+    pub fn marker (&self, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> crate::prelude::Result<BaseEvent> {
+        let wait = wait.into_iter().map(|x| x.as_ref().0).collect::<Vec<_>>();
+        let wait_len = u32::try_from(wait.len()).unwrap();
+        let wait = match wait_len {
+            0 => core::ptr::null(),
+            _ => wait.as_ptr()
+        };
+
+        let mut event = core::ptr::null_mut();
+        let err = unsafe { clEnqueueMarkerWithWaitList(self.0, wait_len, wait, &mut event) };
+
+        if err == 0 {
+            return BaseEvent::new(event);
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "error-stack")] {
+                let err = Error::from(err);
+                let report = error_stack::Report::new(err);
+
+                let report = match err {
+                    Error::InvalidCommandQueue => report.attach_printable(format!("'{:?}' is not a valid command-queue", self.0)),
+                    Error::InvalidContext => report.attach_printable("the context associated with the command queue and events in the event wait list are not the same"),
+                    Error::InvalidEventWaitList => report.attach_printable("event objects in the event wait list are not valid events"),
+                    Error::OutOfResources => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the device"),
+                    Error::OutOfHostMemory => report.attach_printable("there is a failure to allocate resources required by the OpenCL implementation on the host"),
+                    _ => report
+                };
+
+                Err(report)
+            } else {
+                Err(Error::from(err))
+            }
+        }
+    }
+
     #[inline]
     fn get_info<T> (&self, ty: cl_command_queue_info) -> Result<T, Error> {
         let mut result = MaybeUninit::<T>::uninit();
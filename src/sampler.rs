@@ -0,0 +1,196 @@
+use core::{mem::MaybeUninit, fmt::Debug};
+use opencl_sys::{cl_sampler, cl_sampler_info, cl_bool, clRetainSampler, clReleaseSampler, clGetSamplerInfo, clRetainContext, CL_SAMPLER_REFERENCE_COUNT, CL_SAMPLER_CONTEXT, CL_SAMPLER_NORMALIZED_COORDS, CL_SAMPLER_ADDRESSING_MODE, CL_SAMPLER_FILTER_MODE, CL_ADDRESS_NONE, CL_ADDRESS_CLAMP_TO_EDGE, CL_ADDRESS_CLAMP, CL_ADDRESS_REPEAT, CL_ADDRESS_MIRRORED_REPEAT, CL_FILTER_NEAREST, CL_FILTER_LINEAR};
+use crate::prelude::{Result, Error, Context};
+
+#[cfg(feature = "error-stack")]
+use alloc::format;
+
+/// OpenCL sampler. Describes how images are read by kernels.
+#[derive(PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Sampler (pub(crate) cl_sampler);
+
+impl Sampler {
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn new (normalized_coords: bool, addressing_mode: AddressingMode, filter_mode: FilterMode) -> Result<Self> {
+        Self::with_context(Context::default(), normalized_coords, addressing_mode, filter_mode)
+    }
+
+    pub fn with_context (ctx: &Context, normalized_coords: bool, addressing_mode: AddressingMode, filter_mode: FilterMode) -> Result<Self> {
+        let mut err = 0;
+
+        #[cfg(feature = "cl2")]
+        let id = unsafe {
+            let props = [
+                opencl_sys::CL_SAMPLER_NORMALIZED_COORDS as opencl_sys::cl_sampler_properties, normalized_coords as opencl_sys::cl_sampler_properties,
+                opencl_sys::CL_SAMPLER_ADDRESSING_MODE as opencl_sys::cl_sampler_properties, addressing_mode as opencl_sys::cl_sampler_properties,
+                opencl_sys::CL_SAMPLER_FILTER_MODE as opencl_sys::cl_sampler_properties, filter_mode as opencl_sys::cl_sampler_properties,
+                0
+            ];
+
+            opencl_sys::clCreateSamplerWithProperties(ctx.0, props.as_ptr(), &mut err)
+        };
+
+        #[cfg(not(feature = "cl2"))]
+        let id = unsafe {
+            opencl_sys::clCreateSampler(ctx.0, cl_bool::from(normalized_coords), addressing_mode as u32, filter_mode as u32, &mut err)
+        };
+
+        if err == 0 {
+            return Ok(Self(id));
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "error-stack")] {
+                let err = Error::from(err);
+                let report = error_stack::Report::new(err);
+
+                let report = match err {
+                    Error::InvalidContext => report.attach_printable(format!("'{:?}' is not a valid context", ctx.0)),
+                    Error::InvalidValue => report.attach_printable(format!("'{addressing_mode:?}' and/or '{filter_mode:?}' are not valid values, or the combination of values is not valid")),
+                    Error::InvalidOperation => report.attach_printable("images are not supported by any device associated with the context"),
+                    Error::OutOfResources => report.attach_printable("failure to allocate resources required by the OpenCL implementation on the device"),
+                    Error::OutOfHostMemory => report.attach_printable("failure to allocate resources required by the OpenCL implementation on the host"),
+                    _ => report
+                };
+
+                Err(report)
+            } else {
+                Err(Error::from(err))
+            }
+        }
+    }
+
+    /// Return the sampler reference count. The reference count returned should be considered immediately stale. It is unsuitable for general use in applications. This feature is provided for identifying memory leaks.
+    #[inline(always)]
+    pub fn reference_count (&self) -> Result<u32> {
+        self.get_info(CL_SAMPLER_REFERENCE_COUNT)
+    }
+
+    /// Return the context specified when the sampler is created.
+    #[inline(always)]
+    pub fn context (&self) -> Result<Context> {
+        let ctx : Context = self.get_info(CL_SAMPLER_CONTEXT)?;
+        unsafe { tri_panic!(clRetainContext(ctx.0)); }
+        Ok(ctx)
+    }
+
+    /// Return the normalized coords value associated with the sampler.
+    #[inline(always)]
+    pub fn normalized_coords (&self) -> Result<bool> {
+        let v = self.get_info::<cl_bool>(CL_SAMPLER_NORMALIZED_COORDS)?;
+        Ok(v != 0)
+    }
+
+    /// Return the addressing mode value associated with the sampler.
+    #[inline(always)]
+    pub fn addressing_mode (&self) -> Result<AddressingMode> {
+        self.get_info(CL_SAMPLER_ADDRESSING_MODE)
+    }
+
+    /// Return the filter mode value associated with the sampler.
+    #[inline(always)]
+    pub fn filter_mode (&self) -> Result<FilterMode> {
+        self.get_info(CL_SAMPLER_FILTER_MODE)
+    }
+
+    #[inline]
+    fn get_info<T> (&self, ty: cl_sampler_info) -> Result<T> {
+        let mut value = MaybeUninit::<T>::uninit();
+        
+        unsafe {
+            let err = clGetSamplerInfo(self.0, ty, core::mem::size_of::<T>(), value.as_mut_ptr().cast(), core::ptr::null_mut());
+            self.parse_error(err, ty, core::mem::size_of::<T>())?;
+            Ok(value.assume_init())
+        }
+    }
+
+    #[allow(unused_variables)]
+    fn parse_error (&self, err: i32, ty: cl_sampler_info, size: usize) -> Result<()> {
+        if err == 0 {
+            return Ok(());
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "error-stack")] {
+                let err = Error::from(err);
+                let report = error_stack::Report::new(err);
+
+                let report = match err {
+                    Error::InvalidSampler => report.attach_printable(format!("'{:?}' is not a valid sampler", self.0)),
+                    Error::InvalidValue => report.attach_printable(format!("'{ty}' is not one of the supported values or size in bytes specified by {size} is < size of return type and '{ty}' is not a NULL value")),
+                    Error::OutOfResources => report.attach_printable("failure to allocate resources required by the OpenCL implementation on the device"),
+                    Error::OutOfHostMemory => report.attach_printable("failure to allocate resources required by the OpenCL implementation on the host"),
+                    _ => report
+                };
+
+                Err(report)
+            } else {
+                Err(Error::from(err))
+            }
+        }
+    }
+}
+
+impl Debug for Sampler {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Sampler")
+        .field("id", &self.0)
+        .field("normalized_coords", &self.normalized_coords())
+        .field("addressing_mode", &self.addressing_mode())
+        .field("filter_mode", &self.filter_mode())
+        .finish()
+    }
+}
+
+impl Clone for Sampler {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        unsafe {
+            tri_panic!(clRetainSampler(self.0))
+        }
+
+        Self(self.0)
+    }
+}
+
+impl Drop for Sampler {
+    #[inline(always)]
+    fn drop (&mut self) {
+        unsafe {
+            tri_panic!(clReleaseSampler(self.0));
+        }
+    }
+}
+
+unsafe impl Send for Sampler {}
+unsafe impl Sync for Sampler {}
+
+/// Specifies how out-of-range image coordinates are handled when reading an image
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(u32)]
+pub enum AddressingMode {
+    /// Behavior is undefined for out-of-range image coordinates
+    None = CL_ADDRESS_NONE,
+    /// Out-of-range image coordinates are clamped to the edge of the image
+    ClampToEdge = CL_ADDRESS_CLAMP_TO_EDGE,
+    /// Out-of-range image coordinates return the border color
+    #[default]
+    Clamp = CL_ADDRESS_CLAMP,
+    /// Out-of-range image coordinates wrap around. Only valid with normalized coordinates.
+    Repeat = CL_ADDRESS_REPEAT,
+    /// Out-of-range image coordinates are mirrored. Only valid with normalized coordinates.
+    MirroredRepeat = CL_ADDRESS_MIRRORED_REPEAT
+}
+
+/// Specifies the type of filter that is applied when reading an image
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(u32)]
+pub enum FilterMode {
+    /// Returns the image element nearest to the image coordinate
+    #[default]
+    Nearest = CL_FILTER_NEAREST,
+    /// Returns a weighted average of the image elements around the image coordinate
+    Linear = CL_FILTER_LINEAR
+}
\ No newline at end of file
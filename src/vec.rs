@@ -0,0 +1,369 @@
+use core::ops::{RangeBounds, Bound};
+use alloc::vec::Vec;
+use crate::{prelude::{Result, Error, Context, CommandQueue, BaseEvent, MemBuffer}, buffer::{MemFlag, DeviceElement}, event::{WriteBuffer, CopyBuffer}};
+
+#[cfg(feature = "error-stack")]
+use alloc::format;
+
+/// Growable OpenCL buffer, analogous to [```Vec```]. The elements in ```0..len``` are initialized, while the rest of the capacity is not.
+///
+/// Every operation is enqueued on a command queue and returns the event of its last command, so operations can be chained with wait lists.
+pub struct DeviceVec<T: DeviceElement> {
+    ctx: Context,
+    flags: MemFlag,
+    inner: Option<MemBuffer<T>>,
+    len: usize,
+    cap: usize
+}
+
+impl<T: DeviceElement> DeviceVec<T> {
+    /// Creates a new, empty vector. No device memory is allocated until elements are pushed into it.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn new (flags: MemFlag) -> Self {
+        Self::with_context(Context::default(), flags)
+    }
+
+    /// Creates a new, empty vector. No device memory is allocated until elements are pushed into it.
+    #[inline(always)]
+    pub fn with_context (ctx: &Context, flags: MemFlag) -> Self {
+        Self {
+            ctx: ctx.clone(),
+            flags: flags - (MemFlag::COPY_HOST_PTR | MemFlag::USE_HOST_PTR),
+            inner: None,
+            len: 0,
+            cap: 0
+        }
+    }
+
+    /// Creates a new, empty vector with space for at least ```cap``` elements.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn with_capacity (cap: usize, flags: MemFlag) -> Result<Self> {
+        Self::with_capacity_and_context(Context::default(), cap, flags)
+    }
+
+    /// Creates a new, empty vector with space for at least ```cap``` elements.
+    pub fn with_capacity_and_context (ctx: &Context, cap: usize, flags: MemFlag) -> Result<Self> {
+        let mut this = Self::with_context(ctx, flags);
+        if cap > 0 {
+            this.inner = Some(unsafe { MemBuffer::uninit_with_context(ctx, cap, this.flags)? });
+            this.cap = cap;
+        }
+
+        Ok(this)
+    }
+
+    /// Creates a vector whose elements are the contents of ```buffer```
+    pub fn from_buffer (buffer: MemBuffer<T>) -> Result<Self> {
+        let len = buffer.len()?;
+        let flags = buffer.flags()? - (MemFlag::COPY_HOST_PTR | MemFlag::USE_HOST_PTR);
+
+        Ok(Self {
+            ctx: buffer.context()?,
+            flags,
+            inner: Some(buffer),
+            len,
+            cap: len
+        })
+    }
+
+    /// Returns the number of initialized elements in the vector
+    #[inline(always)]
+    pub fn len (&self) -> usize {
+        self.len
+    }
+
+    /// Returns ```true``` if the vector contains no elements
+    #[inline(always)]
+    pub fn is_empty (&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements the vector can hold without reallocating
+    #[inline(always)]
+    pub fn capacity (&self) -> usize {
+        self.cap
+    }
+
+    /// Returns the context of the vector
+    #[inline(always)]
+    pub fn context (&self) -> &Context {
+        &self.ctx
+    }
+
+    /// Returns the underlying buffer, if any has been allocated. Only the first [```len```](DeviceVec::len) elements of the buffer are initialized.
+    #[inline(always)]
+    pub fn as_buffer (&self) -> Option<&MemBuffer<T>> {
+        self.inner.as_ref()
+    }
+
+    /// Returns the underlying buffer, if any has been allocated. Only the first [```len```](DeviceVec::len) elements of the buffer are initialized.
+    #[inline(always)]
+    pub fn as_buffer_mut (&mut self) -> Option<&mut MemBuffer<T>> {
+        self.inner.as_mut()
+    }
+
+    /// Reserves capacity for at least ```additional``` more elements.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn reserve (&mut self, additional: usize, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        self.reserve_with_queue(CommandQueue::default(), additional, wait)
+    }
+
+    /// Reserves capacity for at least ```additional``` more elements.
+    pub fn reserve_with_queue (&mut self, queue: &CommandQueue, additional: usize, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        let wait = collect_wait(wait);
+        match self.grow_for(queue, additional, &wait)? {
+            Some(evt) => Ok(evt),
+            None => queue.marker(wait)
+        }
+    }
+
+    /// Shrinks the capacity of the vector to its length.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn shrink_to_fit (&mut self, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        self.shrink_to_fit_with_queue(CommandQueue::default(), wait)
+    }
+
+    /// Shrinks the capacity of the vector to its length.
+    pub fn shrink_to_fit_with_queue (&mut self, queue: &CommandQueue, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        let wait = collect_wait(wait);
+        if self.cap == self.len {
+            return queue.marker(wait);
+        }
+
+        self.realloc(queue, self.len, &wait)
+    }
+
+    /// Shortens the vector to ```len``` elements. Has no effect if ```len``` is greater than the vector's current length. The capacity of the vector is unchanged.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn truncate (&mut self, len: usize, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        self.truncate_with_queue(CommandQueue::default(), len, wait)
+    }
+
+    /// Shortens the vector to ```len``` elements. Has no effect if ```len``` is greater than the vector's current length. The capacity of the vector is unchanged.
+    #[inline(always)]
+    pub fn truncate_with_queue (&mut self, queue: &CommandQueue, len: usize, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        self.len = self.len.min(len);
+        queue.marker(wait)
+    }
+
+    /// Appends the elements of ```src``` to the end of the vector, reallocating if needed.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn push_slice<'a> (&mut self, src: &'a [T], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<WriteBuffer<'a, '_>> {
+        self.push_slice_with_queue(CommandQueue::default(), src, wait)
+    }
+
+    /// Appends the elements of ```src``` to the end of the vector, reallocating if needed.
+    pub fn push_slice_with_queue<'a> (&mut self, queue: &CommandQueue, src: &'a [T], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<WriteBuffer<'a, '_>> {
+        // OpenCL doesn't allow empty writes
+        if src.is_empty() {
+            return Ok(unsafe { WriteBuffer::from_inner(queue.marker(wait)?) })
+        }
+
+        let mut wait = collect_wait(wait);
+        if let Some(evt) = self.grow_for(queue, src.len(), &wait)? {
+            wait = alloc::vec![evt];
+        }
+
+        let evt = WriteBuffer::new(queue, false, self.len, src, self.inner.as_mut().unwrap(), wait)?;
+        self.len += src.len();
+        Ok(evt)
+    }
+
+    /// Appends the elements of ```src``` to the end of the vector, reallocating if needed. Equivalent to [```push_slice```](DeviceVec::push_slice).
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn extend_from_slice<'a> (&mut self, src: &'a [T], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<WriteBuffer<'a, '_>> {
+        self.push_slice(src, wait)
+    }
+
+    /// Appends the elements of ```src``` to the end of the vector, reallocating if needed. Equivalent to [```push_slice_with_queue```](DeviceVec::push_slice_with_queue).
+    #[inline(always)]
+    pub fn extend_from_slice_with_queue<'a> (&mut self, queue: &CommandQueue, src: &'a [T], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<WriteBuffer<'a, '_>> {
+        self.push_slice_with_queue(queue, src, wait)
+    }
+
+    /// Inserts the elements of ```src``` at position ```idx```, shifting all the elements after it to the right.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn insert_range<'a> (&mut self, idx: usize, src: &'a [T], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<WriteBuffer<'a, '_>> {
+        self.insert_range_with_queue(CommandQueue::default(), idx, src, wait)
+    }
+
+    /// Inserts the elements of ```src``` at position ```idx```, shifting all the elements after it to the right.
+    /// # Errors
+    /// Returns [```Error::InvalidValue```] if ```idx``` is greater than the vector's length
+    pub fn insert_range_with_queue<'a> (&mut self, queue: &CommandQueue, idx: usize, src: &'a [T], wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<WriteBuffer<'a, '_>> {
+        if idx > self.len {
+            #[cfg(feature = "error-stack")]
+            return Err(error_stack::Report::new(Error::InvalidValue).attach_printable(format!("insertion index (is {idx}) should be <= len (is {})", self.len)));
+            #[cfg(not(feature = "error-stack"))]
+            return Err(Error::InvalidValue);
+        }
+
+        // OpenCL doesn't allow empty writes
+        if src.is_empty() {
+            return Ok(unsafe { WriteBuffer::from_inner(queue.marker(wait)?) })
+        }
+
+        let mut wait = collect_wait(wait);
+        let tail = self.len - idx;
+        let required = self.len.checked_add(src.len()).expect("Capacity overflow");
+        let cap = self.grown_capacity(required);
+
+        match self.inner.as_mut() {
+            Some(inner) if required <= self.cap => {
+                if tail > 0 {
+                    // OpenCL doesn't allow overlapping copies, so the tail is shifted through a temporary buffer.
+                    // the old tail is left in place, so the contents are still valid if the write fails
+                    let mut tmp = unsafe { MemBuffer::<T>::uninit_with_context(&self.ctx, tail, self.flags)? };
+                    let evt = CopyBuffer::new(queue, idx, 0, tail, inner, &mut tmp, &wait)?.as_ref().clone();
+                    wait = alloc::vec![CopyBuffer::new(queue, 0, idx + src.len(), tail, &tmp, inner, [evt])?.as_ref().clone()];
+                }
+            },
+
+            old => {
+                // move the head and the tail into a new allocation, leaving a gap for the new elements.
+                // the new allocation only replaces the old one once the write has been enqueued
+                let mut new = unsafe { MemBuffer::<T>::uninit_with_context(&self.ctx, cap, self.flags)? };
+                let mut evts = Vec::with_capacity(2);
+
+                if let Some(old) = old {
+                    if idx > 0 {
+                        evts.push(CopyBuffer::new(queue, 0, 0, idx, old, &mut new, &wait)?.as_ref().clone());
+                    }
+
+                    if tail > 0 {
+                        evts.push(CopyBuffer::new(queue, idx, idx + src.len(), tail, old, &mut new, &wait)?.as_ref().clone());
+                    }
+                }
+
+                if !evts.is_empty() {
+                    wait = evts;
+                }
+
+                let evt = WriteBuffer::new(queue, false, idx, src, &mut new, wait)?.as_ref().clone();
+                self.inner = Some(new);
+                self.cap = cap;
+                self.len = required;
+                return Ok(unsafe { WriteBuffer::from_inner(evt) })
+            }
+        }
+
+        let evt = WriteBuffer::new(queue, false, idx, src, self.inner.as_mut().unwrap(), wait)?;
+        self.len = required;
+        Ok(evt)
+    }
+
+    /// Removes the elements inside ```range```, shifting all the elements after it to the left. The capacity of the vector is unchanged.
+    #[cfg(feature = "def")]
+    #[inline(always)]
+    pub fn drain_range (&mut self, range: impl RangeBounds<usize>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        self.drain_range_with_queue(CommandQueue::default(), range, wait)
+    }
+
+    /// Removes the elements inside ```range```, shifting all the elements after it to the left. The capacity of the vector is unchanged.
+    /// # Errors
+    /// Returns [```Error::InvalidValue```] if ```range``` is out of bounds
+    pub fn drain_range_with_queue (&mut self, queue: &CommandQueue, range: impl RangeBounds<usize>, wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Result<BaseEvent> {
+        let start = match range.start_bound() {
+            Bound::Included(x) => *x,
+            Bound::Excluded(x) => x.saturating_add(1),
+            Bound::Unbounded => 0
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(x) => x.saturating_add(1),
+            Bound::Excluded(x) => *x,
+            Bound::Unbounded => self.len
+        };
+
+        if start > end || end > self.len {
+            #[cfg(feature = "error-stack")]
+            return Err(error_stack::Report::new(Error::InvalidValue).attach_printable(format!("range {start}..{end} is out of bounds for a vector of length {}", self.len)));
+            #[cfg(not(feature = "error-stack"))]
+            return Err(Error::InvalidValue);
+        }
+
+        let tail = self.len - end;
+        if start == end || tail == 0 {
+            self.len -= end - start;
+            return queue.marker(wait);
+        }
+
+        // OpenCL doesn't allow overlapping copies, so the tail is shifted through a temporary buffer
+        let inner = self.inner.as_mut().unwrap();
+        let mut tmp = unsafe { MemBuffer::<T>::uninit_with_context(&self.ctx, tail, self.flags)? };
+        let evt = CopyBuffer::new(queue, end, 0, tail, inner, &mut tmp, wait)?.as_ref().clone();
+        let evt = CopyBuffer::new(queue, 0, start, tail, &tmp, inner, [evt])?.as_ref().clone();
+
+        self.len -= end - start;
+        Ok(evt)
+    }
+
+    #[inline(always)]
+    fn grown_capacity (&self, required: usize) -> usize {
+        required.max(self.cap.saturating_mul(2)).max(1)
+    }
+
+    /// Grows the vector (if needed) so that it can hold ```additional``` more elements, returning the event of the reallocation
+    fn grow_for (&mut self, queue: &CommandQueue, additional: usize, wait: &[BaseEvent]) -> Result<Option<BaseEvent>> {
+        let required = self.len.checked_add(additional).expect("Capacity overflow");
+        if required <= self.cap && self.inner.is_some() {
+            return Ok(None);
+        }
+
+        let cap = self.grown_capacity(required);
+        self.realloc(queue, cap, wait).map(Some)
+    }
+
+    /// Moves the vector's elements into a new allocation of ```cap``` elements
+    fn realloc (&mut self, queue: &CommandQueue, cap: usize, wait: &[BaseEvent]) -> Result<BaseEvent> {
+        debug_assert!(cap >= self.len);
+        if cap == 0 {
+            self.inner = None;
+            self.cap = 0;
+            return queue.marker(wait);
+        }
+
+        let mut new = unsafe { MemBuffer::<T>::uninit_with_context(&self.ctx, cap, self.flags)? };
+        let evt = match self.inner.as_ref() {
+            Some(old) if self.len > 0 => CopyBuffer::new(queue, 0, 0, self.len, old, &mut new, wait)?.as_ref().clone(),
+            _ => queue.marker(wait)?
+        };
+
+        // OpenCL keeps the old buffer alive until the copy has completed
+        self.inner = Some(new);
+        self.cap = cap;
+        Ok(evt)
+    }
+}
+
+impl<T: DeviceElement> TryFrom<MemBuffer<T>> for DeviceVec<T> {
+    type Error = crate::error::ErrorCL;
+
+    #[inline(always)]
+    fn try_from(value: MemBuffer<T>) -> Result<Self> {
+        Self::from_buffer(value)
+    }
+}
+
+impl<T: DeviceElement> core::fmt::Debug for DeviceVec<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DeviceVec")
+            .field("len", &self.len)
+            .field("cap", &self.cap)
+            .field("flags", &self.flags)
+            .finish()
+    }
+}
+
+#[inline(always)]
+fn collect_wait (wait: impl IntoIterator<Item = impl AsRef<BaseEvent>>) -> Vec<BaseEvent> {
+    wait.into_iter().map(|x| x.as_ref().clone()).collect()
+}
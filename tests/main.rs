@@ -1,4 +1,4 @@
-use hlocl::{prelude::*, buffer::{MemFlag, FastRng}, event::various::Swap};
+use hlocl::{prelude::*, buffer::{MemFlag, MemMigrationFlag, MemObjectType, FastRng, RectLayout}, image::Image2D, sampler::{Sampler, AddressingMode, FilterMode}, event::various::Swap, vec::DeviceVec};
 
 static PROGRAM : &str = "void kernel add (const ulong n, __global const float* rhs, __global const float* in, __global float* out) {
     for (ulong id = get_global_id(0); id<n; id += get_global_size(0)) {
@@ -27,5 +27,569 @@ fn main () -> Result<()> {
     let gamma = gamma.to_vec([evt])?.wait()?;
     println!("{gamma:?}");
 
+    Ok(())
+}
+
+#[test]
+fn kernel_args () -> Result<()> {
+    let alpha = MemBuffer::new(&[1f32, 2., 3.], MemFlag::READ_ONLY)?;
+    let beta = MemBuffer::new(&[4f32, 5., 6.], MemFlag::READ_ONLY)?;
+    let gamma = unsafe { MemBuffer::<f32>::uninit(3, MemFlag::WRITE_ONLY) }?;
+
+    let prog = Program::from_source(PROGRAM)?;
+    let mut kernel = unsafe { Kernel::new_unchecked(&prog, "add")? };
+    kernel.set_arg(0, 3u64)?;
+    kernel.set_mem_arg(1, &alpha)?;
+    kernel.set_mem_arg(2, &beta)?;
+    kernel.set_mem_arg(3, &gamma)?;
+    assert_eq!(alpha.reference_count()?, 2);
+
+    // the kernel keeps its arguments alive
+    drop((alpha, beta));
+    let evt = kernel.enqueue(&[3, 1, 1], None, EMPTY)?;
+    assert_eq!(gamma.to_vec([evt])?.wait()?, [5., 7., 9.]);
+
+    // replaced arguments are released
+    kernel.set_mem_arg(3, &MemBuffer::<f32>::new(&[0.; 3], MemFlag::default())?)?;
+    assert_eq!(gamma.reference_count()?, 1);
+    Ok(())
+}
+
+#[test]
+fn slice () -> Result<()> {
+    let mut buffer = MemBuffer::new(&[1u32, 2, 3, 4, 5, 6, 7, 8], MemFlag::default())?;
+    
+    let mut slice = buffer.slice_mut(..4)?;
+    slice.write(0, &[9, 9], EMPTY)?.wait()?;
+    assert_eq!(slice.len()?, 4);
+    assert_eq!(slice.parent()?.map(|x| x.len()).transpose()?, Some(8));
+    
+    let info = slice.info()?;
+    assert_eq!(info.ty, MemObjectType::Buffer);
+    assert_eq!(info.parent.map(|x| x.len), Some(8));
+    drop(slice);
+
+    let slice = buffer.slice(..4)?;
+    assert_eq!(slice.to_vec(EMPTY)?.wait()?, vec![9, 9, 3, 4]);
+    Ok(())
+}
+
+
+#[test]
+fn map () -> Result<()> {
+    let mut buffer = MemBuffer::new(&[1u32, 2, 3, 4], MemFlag::default())?;
+
+    let mut guard = buffer.map_mut(1..3, EMPTY)?.wait()?;
+    assert_eq!(&guard[..], &[2, 3]);
+    guard[0] = 7;
+    guard.unmap(EMPTY)?.wait()?;
+
+    let guard = buffer.map(.., EMPTY)?.wait()?;
+    assert_eq!(&guard[..], &[1, 7, 3, 4]);
+    Ok(())
+}
+
+#[test]
+fn fill () -> Result<()> {
+    let mut buffer = MemBuffer::new(&[0u16; 8], MemFlag::default())?;
+    buffer.fill(3, ..4, EMPTY)?.wait()?;
+    buffer.fill_pattern(&[1, 2], 4.., EMPTY)?.wait()?;
+    assert_eq!(buffer.to_vec(EMPTY)?.wait()?, vec![3, 3, 3, 3, 1, 2, 1, 2]);
+    assert!(buffer.fill_pattern(&[1, 2, 3], .., EMPTY).is_err());
+    Ok(())
+}
+
+#[test]
+fn rect () -> Result<()> {
+    // 4x4 row-major matrix
+    let mut buffer = MemBuffer::new(&(0..16u32).collect::<Vec<_>>(), MemFlag::default())?;
+    
+    let mut tile = [0u32; 4];
+    buffer.read_rect(RectLayout::new_2d(1, 1, 4), &mut tile, RectLayout::default(), [2, 2, 1], EMPTY)?.wait()?;
+    assert_eq!(tile, [5, 6, 9, 10]);
+
+    buffer.write_rect(RectLayout::new_2d(2, 2, 4), &[0, 0, 0, 0], RectLayout::default(), [2, 2, 1], EMPTY)?.wait()?;
+    assert_eq!(buffer.get(15, EMPTY)?.wait()?, 0);
+    assert!(buffer.read_rect(RectLayout::default(), &mut tile, RectLayout::default(), [3, 3, 1], EMPTY).is_err());
+    Ok(())
+}
+
+#[test]
+fn image () -> Result<()> {
+    let pixels = (0..16).map(|x| [x as f32; 4]).collect::<Vec<_>>();
+    let mut image = Image2D::new(&pixels, 4, 4, MemFlag::default())?;
+    assert_eq!(image.dims()?, [4, 4, 1]);
+
+    image.fill([1., 2., 3., 4.], [0, 0, 0], [2, 2, 1], EMPTY)?.wait()?;
+    let tile = image.read([1, 1, 0], [2, 1, 1], EMPTY)?.wait()?;
+    assert_eq!(tile, vec![[1., 2., 3., 4.], [6.; 4]]);
+
+    let mut buffer = unsafe { MemBuffer::<[f32; 4]>::uninit(16, MemFlag::default())? };
+    image.copy_to_buffer([0; 3], [4, 4, 1], &mut buffer, 0, EMPTY)?.wait()?;
+    assert_eq!(buffer.get(15, EMPTY)?.wait()?, [15.; 4]);
+    Ok(())
+}
+
+#[test]
+fn sampler () -> Result<()> {
+    let sampler = Sampler::new(true, AddressingMode::Repeat, FilterMode::Linear)?;
+    assert!(sampler.normalized_coords()?);
+    assert_eq!(sampler.addressing_mode()?, AddressingMode::Repeat);
+    assert_eq!(sampler.clone().filter_mode()?, FilterMode::Linear);
+    Ok(())
+}
+
+#[cfg(feature = "cl2")]
+#[test]
+fn pipe () -> Result<()> {
+    let pipe = hlocl::pipe::Pipe::<[f32; 4]>::new(64, MemFlag::default())?;
+    assert_eq!(pipe.packet_size()?, 16);
+    assert_eq!(pipe.max_packets()?, 64);
+    Ok(())
+}
+
+#[test]
+fn migrate () -> Result<()> {
+    let buffer = MemBuffer::new(&[1u8, 2, 3], MemFlag::default())?;
+    let image = Image2D::new(&[0f32; 4], 2, 2, MemFlag::default())?;
+    let queue = CommandQueue::default();
+
+    let evt = buffer.migrate_to(queue, MemMigrationFlag::HOST, EMPTY)?;
+    queue.migrate_all(&[&buffer, &image], MemMigrationFlag::default(), [evt])?.wait()?;
+    assert_eq!(buffer.to_vec(EMPTY)?.wait()?, vec![1, 2, 3]);
+    Ok(())
+}
+
+#[test]
+fn host_vec () -> Result<()> {
+    let buffer = MemBuffer::from_vec(vec![1u32, 2, 3, 4], MemFlag::default())?;
+    assert!(buffer.flags()?.contains(MemFlag::USE_HOST_PTR));
+    assert_eq!(buffer.to_vec(EMPTY)?.wait()?, vec![1, 2, 3, 4]);
+    
+    let (send, recv) = std::sync::mpsc::channel();
+    buffer.on_destroy(move || send.send(()).unwrap())?;
+    drop(buffer);
+    
+    recv.recv_timeout(std::time::Duration::from_secs(5)).unwrap();
+    Ok(())
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn element () -> Result<()> {
+    #[derive(Debug, Clone, Copy, PartialEq, hlocl::buffer::DeviceElement)]
+    #[repr(C)]
+    struct Particle {
+        pos: [f32; 2],
+        id: u32,
+        mass: f32
+    }
+
+    let particles = [Particle { pos: [1., 2.], id: 0, mass: 3. }, Particle { pos: [4., 5.], id: 1, mass: 6. }];
+    let buffer = MemBuffer::new(&particles, MemFlag::default())?;
+    assert_eq!(buffer.as_bytes().len()?, 32);
+
+    let mut bytes = Vec::new();
+    buffer.write_into(&mut bytes, EMPTY).unwrap();
+    let copy = MemBuffer::<Particle>::from_io(MemFlag::default(), &mut bytes.as_slice())?;
+    assert_eq!(copy.to_vec(EMPTY)?.wait()?, particles);
+    Ok(())
+}
+
+#[test]
+fn cast () -> Result<()> {
+    let mut buffer = MemBuffer::new(&[1f32, 2., 3., 4., 5., 6., 7., 8.], MemFlag::default())?;
+    assert_eq!(buffer.cast_ref::<[f32; 4]>()?.len()?, 2);
+    assert!(buffer.cast_ref::<[f32; 3]>().is_err());
+
+    buffer.cast_mut::<u32>()?.set(0, 0, EMPTY)?.wait()?;
+    assert_eq!(buffer.as_bytes().len()?, 32);
+
+    let vec4 = buffer.cast::<[f32; 4]>()?;
+    assert_eq!(vec4.to_vec(EMPTY)?.wait()?, vec![[0., 2., 3., 4.], [5., 6., 7., 8.]]);
+    Ok(())
+}
+
+#[test]
+fn device_vec () -> Result<()> {
+    let mut vec = DeviceVec::<u32>::new(MemFlag::default());
+    vec.push_slice(&[1, 2, 3], EMPTY)?.wait()?;
+    vec.extend_from_slice(&[4, 5, 6, 7], EMPTY)?.wait()?;
+    assert_eq!(vec.len(), 7);
+    assert!(vec.capacity() >= 7);
+
+    vec.insert_range(1, &[10, 11], EMPTY)?.wait()?;
+    vec.drain_range(4..6, EMPTY)?.wait()?;
+    vec.truncate(6, EMPTY)?.wait()?;
+    vec.shrink_to_fit(EMPTY)?.wait()?;
+
+    assert_eq!(vec.capacity(), 6);
+    assert_eq!(vec.as_buffer().unwrap().to_vec(EMPTY)?.wait()?, vec![1, 10, 11, 2, 5, 6]);
+    Ok(())
+}
+
+#[test]
+fn device_vec_empty_src () -> Result<()> {
+    let mut vec = DeviceVec::<u32>::new(MemFlag::default());
+    vec.push_slice(&[], EMPTY)?.wait()?;
+    vec.insert_range(0, &[], EMPTY)?.wait()?;
+    assert!(vec.is_empty());
+    assert_eq!(vec.capacity(), 0);
+    assert!(vec.as_buffer().is_none());
+
+    vec.push_slice(&[1, 2], EMPTY)?.wait()?;
+    vec.insert_range(1, &[], EMPTY)?.wait()?;
+    vec.push_slice(&[], EMPTY)?.wait()?;
+    assert_eq!(vec.len(), 2);
+    assert_eq!(vec.as_buffer().unwrap().to_vec(EMPTY)?.wait()?, vec![1, 2]);
+    Ok(())
+}
+
+#[test]
+fn copy_to () -> Result<()> {
+    // offsets and lengths are in elements, not bytes
+    let src = MemBuffer::new(&[1u32, 2, 3, 4], MemFlag::READ_ONLY)?;
+    let mut dst = MemBuffer::new(&[0u32; 6], MemFlag::default())?;
+    src.copy_to(1, &mut dst, 2..5, EMPTY)?.wait()?;
+    assert_eq!(dst.to_vec(EMPTY)?.wait()?, vec![0, 0, 2, 3, 4, 0]);
+
+    // open-ended ranges cover the destination
+    let mut short = MemBuffer::new(&[0u32; 2], MemFlag::default())?;
+    src.copy_to(1, &mut short, .., EMPTY)?.wait()?;
+    assert_eq!(short.to_vec(EMPTY)?.wait()?, vec![2, 3]);
+    assert!(src.copy_to(0, &mut dst, .., EMPTY).is_err());
+    assert!(src.copy_to(3, &mut short, .., EMPTY).is_err());
+    Ok(())
+}
+
+#[test]
+fn clone () -> Result<()> {
+    let buffer = MemBuffer::new(&[1u32, 2, 3, 4], MemFlag::READ_ONLY)?;
+    let copy = buffer.try_clone(EMPTY)?.wait()?;
+    assert_eq!(copy.flags()?, MemFlag::READ_ONLY);
+    assert_eq!(copy.to_vec(EMPTY)?.wait()?, vec![1, 2, 3, 4]);
+
+    let slice = buffer.slice(2..)?;
+    assert_eq!(slice.clone().to_vec(EMPTY)?.wait()?, vec![3, 4]);
+    assert_eq!(MemBuffer::clone(&slice).to_vec(EMPTY)?.wait()?, vec![3, 4]);
+    Ok(())
+}
+
+#[test]
+fn io () -> Result<()> {
+    use std::io::{Read, Write, Seek, SeekFrom};
+
+    let data = (0..=255u8).cycle().take(1000).collect::<Vec<_>>();
+    let mut buffer = MemBuffer::<u32>::from_reader_sized(250, MemFlag::default(), &mut data.as_slice())?;
+
+    let mut reader = buffer.reader(64);
+    reader.seek(SeekFrom::Start(500)).unwrap();
+    let mut read = Vec::new();
+    reader.read_to_end(&mut read).unwrap();
+    assert_eq!(read, data[500..]);
+
+    let mut writer = buffer.writer(64);
+    writer.seek(SeekFrom::End(-4)).unwrap();
+    writer.write_all(&7u32.to_ne_bytes()).unwrap();
+    assert_eq!(writer.write(&[1]).unwrap(), 0);
+    writer.flush().unwrap();
+    drop(writer);
+
+    assert_eq!(buffer.get(249, EMPTY)?.wait()?, 7);
+    assert!(MemBuffer::<u32>::from_reader_sized(251, MemFlag::default(), &mut data.as_slice()).is_err());
+    Ok(())
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn async_io () -> Result<()> {
+    use futures::{AsyncReadExt, AsyncWriteExt, AsyncSeekExt, io::SeekFrom};
+
+    let data = (0..=255u8).cycle().take(1000).collect::<Vec<_>>();
+    let mut buffer = MemBuffer::<u32>::from_reader_sized_async(250, MemFlag::default(), &mut data.as_slice()).await?;
+
+    let mut reader = buffer.async_reader(64);
+    reader.seek(SeekFrom::Start(100)).await.unwrap();
+    let mut read = Vec::new();
+    reader.read_to_end(&mut read).await.unwrap();
+    assert_eq!(read, data[100..]);
+    drop(reader);
+
+    let mut writer = buffer.async_writer(64);
+    writer.write_all(&[9; 200]).await.unwrap();
+    writer.close().await.unwrap();
+    drop(writer);
+
+    // unflushed data is written on drop
+    let mut writer = buffer.async_writer(64);
+    writer.seek(SeekFrom::Start(300)).await.unwrap();
+    writer.write_all(&[7; 10]).await.unwrap();
+    drop(writer);
+
+    let mut out = Vec::new();
+    buffer.write_into_async(&mut out, EMPTY).await?;
+    assert_eq!(out[..200], [9; 200]);
+    assert_eq!(out[200..300], data[200..300]);
+    assert_eq!(out[300..310], [7; 10]);
+    assert_eq!(out[310..], data[310..]);
+    Ok(())
+}
+
+#[cfg(feature = "npy")]
+#[test]
+fn npy () -> Result<()> {
+    use std::io::Cursor;
+    use hlocl::buffer::{NpyError, NpzArchive, NpzWriter};
+
+    let buffer = MemBuffer::new(&[[1f32, 2., 3.], [4., 5., 6.]], MemFlag::default())?;
+    let mut file = Vec::new();
+    buffer.save_npy(&mut file, EMPTY).unwrap();
+    assert!(file.starts_with(b"\x93NUMPY"));
+
+    let loaded = MemBuffer::<[f32; 3]>::load_npy(MemFlag::default(), &mut file.as_slice()).unwrap();
+    assert_eq!(loaded.to_vec(EMPTY)?.wait()?, buffer.to_vec(EMPTY)?.wait()?);
+    let flat = MemBuffer::<f32>::load_npy(MemFlag::default(), &mut file.as_slice()).unwrap();
+    assert_eq!(flat.len()?, 6);
+    assert!(matches!(MemBuffer::<f64>::load_npy(MemFlag::default(), &mut file.as_slice()), Err(NpyError::DtypeMismatch { .. })));
+
+    // shapes whose size overflows are rejected
+    let header = format!("{{'descr': '<f4', 'fortran_order': False, 'shape': ({}, 2), }}\n", usize::MAX);
+    let mut huge = b"\x93NUMPY\x01\x00".to_vec();
+    huge.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    huge.extend_from_slice(header.as_bytes());
+    assert!(matches!(MemBuffer::<f32>::load_npy(MemFlag::default(), &mut huge.as_slice()), Err(NpyError::InvalidHeader(_))));
+
+    let ints = MemBuffer::new(&[1i64, -2, 3], MemFlag::default())?;
+    let mut npz = NpzWriter::new(Cursor::new(Vec::new()));
+    npz.add("floats", &buffer, EMPTY).unwrap();
+    npz.add("ints", &ints, EMPTY).unwrap();
+    let npz = npz.finish().unwrap();
+
+    let mut npz = NpzArchive::new(npz).unwrap();
+    assert_eq!(npz.len(), 2);
+    assert!(npz.names().any(|x| x == "ints"));
+    let loaded = npz.load::<i64>("ints", MemFlag::default()).unwrap();
+    assert_eq!(loaded.to_vec(EMPTY)?.wait()?, [1, -2, 3]);
+    assert!(matches!(npz.load::<i64>("missing", MemFlag::default()), Err(NpyError::NotFound(_))));
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_bytes () -> Result<()> {
+    use serde::de::value::{BytesDeserializer, SeqDeserializer, Error};
+
+    let buffer = MemBuffer::new(&[1.5f32, -2., 3.25], MemFlag::default())?;
+    let json = hlocl::buffer::serde_bytes::serialize(&buffer, serde_json::value::Serializer).unwrap();
+    assert_eq!(json.as_array().unwrap().len(), hlocl::buffer::serde_bytes::HEADER_LEN + 12);
+
+    let text = json.to_string();
+    let mut de = serde_json::Deserializer::from_str(&text);
+    let loaded: MemBuffer<f32> = hlocl::buffer::serde_bytes::deserialize(&mut de).unwrap();
+    assert_eq!(loaded.to_vec(EMPTY)?.wait()?, [1.5, -2., 3.25]);
+
+    let blob = json.as_array().unwrap().iter().map(|x| x.as_u64().unwrap() as u8).collect::<Vec<_>>();
+    let loaded: MemBuffer<u32> = hlocl::buffer::serde_bytes::deserialize(BytesDeserializer::<Error>::new(&blob)).unwrap();
+    assert_eq!(loaded.len()?, 3);
+    assert!(hlocl::buffer::serde_bytes::deserialize::<u64, _>(BytesDeserializer::<Error>::new(&blob)).is_err());
+    assert!(hlocl::buffer::serde_bytes::deserialize::<u32, _>(BytesDeserializer::<Error>::new(&blob[..20])).is_err());
+
+    // sequences are checked against the header's length
+    let seq = SeqDeserializer::<_, Error>::new(blob.iter().copied());
+    assert_eq!(hlocl::buffer::serde_bytes::deserialize::<u32, _>(seq).unwrap().len()?, 3);
+    let seq = SeqDeserializer::<_, Error>::new(blob.iter().copied().chain(core::iter::repeat(0)));
+    assert!(hlocl::buffer::serde_bytes::deserialize::<u32, _>(seq).is_err());
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn seed () -> Result<()> {
+    use std::collections::HashMap;
+    use serde::de::{DeserializeSeed, value::{SeqDeserializer, Error}};
+    use hlocl::buffer::BufferSeed;
+
+    let seed = BufferSeed::<u32>::with_flags(MemFlag::READ_ONLY);
+    let exact = seed.deserialize(SeqDeserializer::<_, Error>::new(0..10u32)).unwrap();
+    assert_eq!(exact.to_vec(EMPTY)?.wait()?, (0..10).collect::<Vec<_>>());
+    assert_eq!(exact.flags()? & MemFlag::READ_ONLY, MemFlag::READ_ONLY);
+
+    // larger than the upfront allocation
+    let grown = seed.deserialize(SeqDeserializer::<_, Error>::new(0..(5u32 << 20))).unwrap();
+    assert_eq!(grown.len()?, 5 << 20);
+    assert_eq!(grown.get((5 << 20) - 1, EMPTY)?.wait()?, (5 << 20) - 1);
+
+    // the announced length isn't allocated upfront, and is checked against the actual one
+    struct Lying(std::ops::Range<u32>);
+    impl Iterator for Lying {
+        type Item = u32;
+
+        fn next (&mut self) -> Option<u32> {
+            self.0.next()
+        }
+
+        fn size_hint (&self) -> (usize, Option<usize>) {
+            (1 << 40, Some(1 << 40))
+        }
+    }
+
+    assert!(seed.deserialize(SeqDeserializer::<_, Error>::new(Lying(0..3))).is_err());
+
+    let mut de = serde_json::Deserializer::from_str("{\"a\": [1, 2], \"b\": [3]}");
+    let map: HashMap<String, MemBuffer<u32>> = seed.map().deserialize(&mut de).unwrap();
+    assert_eq!(map["b"].to_vec(EMPTY)?.wait()?, [3]);
+
+    let mut de = serde_json::Deserializer::from_str("[[1, 2], [3, 4, 5]]");
+    let vec = seed.vec().deserialize(&mut de).unwrap();
+    assert_eq!(vec.len(), 2);
+    assert_eq!(vec[1].len()?, 3);
+    Ok(())
+}
+
+#[test]
+fn pool () -> Result<()> {
+    use hlocl::buffer::BufferPool;
+
+    let pool = BufferPool::new(1 << 20);
+    let mut buffer = unsafe { pool.uninit::<f32>(100, MemFlag::default())? };
+    assert_eq!(buffer.len()?, 100);
+    let fill = buffer.view_mut().fill(1., .., EMPTY)?.as_ref().clone();
+    buffer.track(fill);
+    drop(buffer);
+
+    let other = unsafe { pool.uninit::<u32>(90, MemFlag::default())? };
+    assert_eq!(other.len()?, 90);
+    let stats = pool.stats();
+    assert_eq!((stats.hits, stats.misses, stats.cached_buffers), (1, 1, 0));
+    drop(other);
+
+    assert_eq!(pool.stats().cached_bytes, 512);
+    pool.trim();
+    assert_eq!(pool.stats().cached_buffers, 0);
+
+    pool.set_max_cached(0);
+    drop(unsafe { pool.uninit::<u8>(16, MemFlag::READ_ONLY)? });
+    assert_eq!(pool.stats().cached_buffers, 0);
+    Ok(())
+}
+
+#[cfg(feature = "reduce")]
+#[test]
+fn reduce () -> Result<()> {
+    let data = (1..=10_000i32).map(|x| x % 97 - 40).collect::<Vec<_>>();
+    let buffer = MemBuffer::new(&data, MemFlag::READ_ONLY)?;
+
+    assert_eq!(buffer.sum(EMPTY)?.wait()?, data.iter().sum::<i32>());
+    assert_eq!(buffer.min(EMPTY)?.wait()?, -40);
+    assert_eq!(buffer.max(EMPTY)?.wait()?, 56);
+    assert_eq!(buffer.min_max(EMPTY)?.wait()?, (-40, 56));
+
+    let small = MemBuffer::new(&[1.5f32, 2., -4.], MemFlag::READ_ONLY)?;
+    assert_eq!(small.product(EMPTY)?.wait()?, -12.);
+    assert_eq!(small.min_max(EMPTY)?.wait()?, (-4., 2.));
+    Ok(())
+}
+
+#[cfg(feature = "scan")]
+#[test]
+fn scan () -> Result<()> {
+    use hlocl::buffer::ScanOp;
+
+    let data = (0..5000u32).map(|x| x % 7).collect::<Vec<_>>();
+    let buffer = MemBuffer::new(&data, MemFlag::READ_ONLY)?;
+
+    let inclusive = buffer.inclusive_scan(ScanOp::Add, EMPTY)?.wait()?;
+    let expected = data.iter().scan(0, |acc, x| { *acc += x; Some(*acc) }).collect::<Vec<_>>();
+    assert_eq!(inclusive.to_vec(EMPTY)?.wait()?, expected);
+
+    let exclusive = buffer.exclusive_scan(ScanOp::Add, 0, EMPTY)?.wait()?.to_vec(EMPTY)?.wait()?;
+    assert_eq!(exclusive[0], 0);
+    assert_eq!(exclusive[1..], expected[..4999]);
+
+    let max = buffer.inclusive_scan(ScanOp::Custom("max(a, b)"), EMPTY)?.wait()?.to_vec(EMPTY)?.wait()?;
+    assert_eq!(max[..8], [0, 1, 2, 3, 4, 5, 6, 6]);
+    Ok(())
+}
+
+#[cfg(feature = "sort")]
+#[test]
+fn sort () -> Result<()> {
+    use hlocl::buffer::SortOrder;
+
+    let data = (0..3000i32).map(|x| (x * 7919) % 1001 - 500).collect::<Vec<_>>();
+    let mut buffer = MemBuffer::new(&data, MemFlag::READ_WRITE)?;
+    buffer.sort(SortOrder::Ascending, EMPTY)?.wait()?;
+
+    let mut expected = data.clone();
+    expected.sort();
+    assert_eq!(buffer.to_vec(EMPTY)?.wait()?, expected);
+
+    let floats = [3.5f32, -0.0, -7.25, 0.0, 1e-3, -1e9, 42.0];
+    let mut buffer = MemBuffer::new(&floats, MemFlag::READ_WRITE)?;
+    buffer.sort(SortOrder::Descending, EMPTY)?.wait()?;
+    assert_eq!(buffer.to_vec(EMPTY)?.wait()?, [42.0, 3.5, 1e-3, 0.0, -0.0, -7.25, -1e9]);
+
+    // equal keys keep their original order
+    let keys = (0..1000u32).map(|x| x % 10).collect::<Vec<_>>();
+    let values = (0..1000u64).collect::<Vec<_>>();
+    let mut key_buffer = MemBuffer::new(&keys, MemFlag::READ_WRITE)?;
+    let mut value_buffer = MemBuffer::new(&values, MemFlag::READ_WRITE)?;
+    MemBuffer::sort_by_key(&mut key_buffer, &mut value_buffer, SortOrder::Ascending, EMPTY)?.wait()?;
+
+    let mut expected = keys.iter().copied().zip(values.iter().copied()).collect::<Vec<_>>();
+    expected.sort_by_key(|(k, _)| *k);
+    let (keys, values) : (Vec<_>, Vec<_>) = expected.into_iter().unzip();
+    assert_eq!(key_buffer.to_vec(EMPTY)?.wait()?, keys);
+    assert_eq!(value_buffer.to_vec(EMPTY)?.wait()?, values);
+    Ok(())
+}
+
+#[cfg(feature = "ops")]
+#[test]
+fn ops () -> Result<()> {
+    let alpha = MemBuffer::new(&[1f32, 2., 3., 4.], MemFlag::READ_WRITE)?;
+    let beta = MemBuffer::new(&[0.5f32, 0.25, -1., 2.], MemFlag::READ_ONLY)?;
+
+    let sum = (&alpha + &beta)?.wait()?;
+    assert_eq!(sum.to_vec(EMPTY)?.wait()?, [1.5, 2.25, 2., 6.]);
+
+    let scaled = (&alpha * 2.)?.wait()?;
+    assert_eq!(scaled.to_vec(EMPTY)?.wait()?, [2., 4., 6., 8.]);
+
+    let neg = (-&beta)?.wait()?;
+    assert_eq!(neg.to_vec(EMPTY)?.wait()?, [-0.5, -0.25, 1., -2.]);
+
+    let mut alpha = alpha;
+    alpha.div_assign(&beta, EMPTY)?.wait()?;
+    alpha.sub_assign_scalar(1., EMPTY)?.wait()?;
+    assert_eq!(alpha.to_vec(EMPTY)?.wait()?, [1., 7., -4., 1.]);
+
+    let short = MemBuffer::new(&[1f32], MemFlag::READ_ONLY)?;
+    assert!((&alpha - &short).is_err());
+    Ok(())
+}
+
+#[cfg(feature = "expr")]
+#[test]
+fn expr () -> Result<()> {
+    use hlocl::buffer::expr;
+
+    let alpha = MemBuffer::new(&[1f32, 2., 3., 4.], MemFlag::READ_ONLY)?;
+    let beta = MemBuffer::new(&[4f32, 9., 16., 25.], MemFlag::READ_ONLY)?;
+
+    let fused = expr(&alpha) * 2. + expr(&beta).sqrt();
+    assert_eq!(fused.eval(EMPTY)?.wait()?.to_vec(EMPTY)?.wait()?, [4., 7., 10., 13.]);
+
+    // same shape, different scalars
+    let mut out = unsafe { MemBuffer::<f32>::uninit(4, MemFlag::READ_WRITE)? };
+    (expr(&alpha) * 3. + expr(&beta).sqrt()).eval_into(&mut out, EMPTY)?.wait()?;
+    assert_eq!(out.to_vec(EMPTY)?.wait()?, [5., 9., 13., 17.]);
+
+    let bytes = MemBuffer::new(&[200u8, 100, 10], MemFlag::READ_ONLY)?;
+    let wrapped = ((expr(&bytes) + 100u8) / 2).max(20);
+    assert_eq!(wrapped.eval(EMPTY)?.wait()?.to_vec(EMPTY)?.wait()?, [22, 100, 55]);
+
+    let short = MemBuffer::new(&[1f32], MemFlag::READ_ONLY)?;
+    assert!((expr(&alpha) - &short).eval(EMPTY).is_err());
     Ok(())
 }
\ No newline at end of file